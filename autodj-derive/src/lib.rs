@@ -0,0 +1,240 @@
+//! Procedural macros for [`autodj`](https://docs.rs/autodj),
+//! re-exported from there behind the `derive` feature
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::format_ident;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, FnArg, Ident, ItemFn,
+    ReturnType, Type,
+};
+
+/// Lift a function of [`f64`] (or [`f32`]) arguments to a generic function over dual numbers.
+///
+/// Every argument and the return value of the floating-point type become a generic `Dual`
+/// of that value type, while other arguments are kept as is.
+/// Besides the operations of `Dual`, the body may mix dual numbers with plain floats
+/// in arithmetic and comparisons, as `solid::DualNumber` does.
+/// The body itself is not rewritten, so floats have to stay literals or arguments
+///```
+/// use autodj::prelude::array::*;
+/// use autodj::dualize;
+///
+/// #[dualize]
+/// fn spring_energy(k: f64, x: f64, rest: f64) -> f64 {
+///     let stretch = x - rest;
+///     0.5 * k * stretch * stretch
+/// }
+///
+/// let [k, x] = [2.0, 3.0].into_variables();
+/// let energy = spring_energy(k, x, DualNumber::parameter(1.0));
+/// assert_eq!(energy.value(), &4.0);
+/// assert_eq!(energy.dual().as_ref(), &[2.0, 4.0]);
+/// ```
+#[proc_macro_attribute]
+pub fn dualize(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let function = parse_macro_input!(item as ItemFn);
+    dualize_impl(attr.into(), function)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Rewrite the signature of a function as described in [`dualize`]
+fn dualize_impl(attr: TokenStream2, mut function: ItemFn) -> syn::Result<TokenStream2> {
+    if !attr.is_empty() {
+        return Err(Error::new_spanned(attr, "`dualize` takes no arguments"));
+    }
+
+    let mut scalar: Option<Ident> = None;
+    let mut lift = |ty: &mut Type| -> syn::Result<()> {
+        let Some(found) = float_type(ty) else {
+            return Ok(());
+        };
+        match &scalar {
+            Some(known) if *known != found => {
+                return Err(Error::new_spanned(
+                    ty,
+                    "`f32` and `f64` cannot be mixed in a dualized function",
+                ));
+            }
+            Some(_) => {}
+            None => scalar = Some(found),
+        }
+        *ty = parse_quote!(AutodjDual);
+        Ok(())
+    };
+    for input in &mut function.sig.inputs {
+        if let FnArg::Typed(argument) = input {
+            lift(&mut argument.ty)?;
+        }
+    }
+    if let ReturnType::Type(_, output) = &mut function.sig.output {
+        lift(output)?;
+    }
+    let Some(scalar) = scalar else {
+        return Err(Error::new_spanned(
+            &function.sig,
+            "no `f64` or `f32` arguments to dualize",
+        ));
+    };
+
+    function.sig.generics.params.push(parse_quote!(AutodjDual));
+    let where_clause = function.sig.generics.make_where_clause();
+    where_clause.predicates.push(parse_quote! {
+        AutodjDual: ::autodj::fluid::Dual<Value = #scalar>
+            + ::core::marker::Copy
+            + ::core::ops::Neg<Output = AutodjDual>
+            + ::core::ops::Add<#scalar, Output = AutodjDual>
+            + ::core::ops::Sub<#scalar, Output = AutodjDual>
+            + ::core::ops::Mul<#scalar, Output = AutodjDual>
+            + ::core::ops::Div<#scalar, Output = AutodjDual>
+            + ::core::cmp::PartialOrd<#scalar>
+    });
+    where_clause.predicates.push(parse_quote! {
+        #scalar: ::core::ops::Add<AutodjDual, Output = AutodjDual>
+            + ::core::ops::Sub<AutodjDual, Output = AutodjDual>
+            + ::core::ops::Mul<AutodjDual, Output = AutodjDual>
+            + ::core::ops::Div<AutodjDual, Output = AutodjDual>
+    });
+
+    Ok(quote!(#function))
+}
+
+/// Generate a dual counterpart of a struct with named [`f64`] (or [`f32`]) fields.
+///
+/// For `struct Params` this derives
+/// - `ParamsDual<D>` with the same field names and visibility, every field of type `D`,
+/// - `Params::FIELDS` with field names in order of variables,
+/// - `Params::into_variables(self)` turning every field into an independent variable,
+/// - `Params::gradient(&output)` collecting derivatives of an output into `Params`,
+///   so each one is read by field name
+///```
+/// use autodj::prelude::array::*;
+/// use autodj::DualStruct;
+///
+/// #[derive(DualStruct)]
+/// struct Params {
+///     k: f64,
+///     m: f64,
+/// }
+///
+/// let p = Params { k: 2.0, m: 3.0 }.into_variables();
+/// let frequency_squared = p.k / p.m;
+/// let grad = Params::gradient(&frequency_squared);
+/// assert_eq!(Params::FIELDS, ["k", "m"]);
+/// assert_eq!(grad.k, 1.0 / 3.0);
+/// assert_eq!(grad.m, -2.0 / 9.0);
+/// ```
+#[proc_macro_derive(DualStruct)]
+pub fn derive_dual_struct(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    dual_struct_impl(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Generate items described in [`derive_dual_struct`]
+fn dual_struct_impl(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "`DualStruct` does not support generic structs",
+        ));
+    }
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "`DualStruct` supports structs only",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            &data.fields,
+            "`DualStruct` supports named fields only",
+        ));
+    };
+
+    let mut scalar: Option<Ident> = None;
+    for field in &fields.named {
+        let found = float_type(&field.ty).ok_or_else(|| {
+            Error::new_spanned(&field.ty, "`DualStruct` fields must be `f64` or `f32`")
+        })?;
+        if scalar.as_ref().is_some_and(|known| *known != found) {
+            return Err(Error::new_spanned(
+                &field.ty,
+                "`f32` and `f64` fields cannot be mixed",
+            ));
+        }
+        scalar = Some(found);
+    }
+    let Some(scalar) = scalar else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "`DualStruct` needs at least one field",
+        ));
+    };
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let dual_name = format_ident!("{name}Dual");
+    let names: Vec<&Ident> = fields
+        .named
+        .iter()
+        .filter_map(|field| field.ident.as_ref())
+        .collect();
+    let visibilities = fields.named.iter().map(|field| &field.vis);
+    let count = names.len();
+    let labels = names.iter().map(ToString::to_string);
+    let struct_doc = format!("Dual counterpart of [`{name}`]");
+    let field_docs = names
+        .iter()
+        .map(|field| format!("Dual counterpart of `{field}`"));
+
+    Ok(quote! {
+        #[doc = #struct_doc]
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        #vis struct #dual_name<AutodjDual> {
+            #(
+                #[doc = #field_docs]
+                #visibilities #names: AutodjDual,
+            )*
+        }
+
+        impl #name {
+            /// Names of fields in order of variables
+            #vis const FIELDS: [&'static str; #count] = [#(#labels),*];
+
+            /// Turn every field into an independent dual variable
+            #vis fn into_variables(
+                self,
+            ) -> #dual_name<::autodj::solid::array::DualNumber<#scalar, #count>> {
+                let [#(#names),*] =
+                    ::autodj::solid::array::IntoVariables::into_variables([#(self.#names),*]);
+                #dual_name { #(#names),* }
+            }
+
+            /// Derivatives of an output with respect to every field
+            #vis fn gradient(
+                output: &::autodj::solid::array::DualNumber<#scalar, #count>,
+            ) -> Self {
+                let [#(#names),*] = *::autodj::fluid::Dual::dual(output).as_ref();
+                Self { #(#names),* }
+            }
+        }
+    })
+}
+
+/// Name of a bare `f64` or `f32` type
+fn float_type(ty: &Type) -> Option<Ident> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    if path.qself.is_some() {
+        return None;
+    }
+    path.path
+        .get_ident()
+        .filter(|ident| *ident == "f64" || *ident == "f32")
+        .cloned()
+}
@@ -2,7 +2,8 @@
     missing_docs,
     clippy::missing_docs_in_private_items,
     clippy::default_numeric_fallback,
-    clippy::indexing_slicing
+    clippy::indexing_slicing,
+    reason = "example"
 )]
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -19,15 +20,25 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     let calc_residual_time_step = |x| calc_residual_problem(x0_dual, x);
 
-    let x1 = newton_iterations(calc_residual_time_step, x_approx, 10, 1e-3);
+    let report = Newton::new()
+        .max_iterations(10)
+        .tolerance(1e-3)
+        .solve_array(
+            |x: [Dual2; 2]| {
+                let residual = calc_residual_time_step(x.into_s_vector());
+                [residual[0], residual[1]]
+            },
+            [x_approx[0], x_approx[1]],
+        );
 
-    println!("{x1:?}");
+    println!("{report}");
+    println!("{:?}", report.solution());
 
     Ok(())
 }
 
-use autodj::prelude::array::*;
-use nalgebra::{base::Scalar, vector, ArrayStorage, SMatrix, SVector};
+use autodj::{prelude::array::*, solver::newton::Newton};
+use nalgebra::{base::Scalar, vector, ArrayStorage, SVector};
 use std::{
     error::Error,
     f64::consts::PI,
@@ -36,7 +47,6 @@ use std::{
 };
 type Dual2 = DualNumber<f64, 2>;
 type V2<T> = SVector<T, 2>;
-type M2<T> = SMatrix<T, 2, 2>;
 
 fn u_dot<T>(v: T) -> T {
     v
@@ -103,7 +113,7 @@ impl TryFrom<f64> for Fraction {
     }
 }
 
-#[allow(unused)]
+#[allow(unused, reason = "not all schemes are used in the example")]
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
 enum OdeScheme {
@@ -143,50 +153,3 @@ impl<const N: usize, InputArray: Into<[Input; N]>, Input> IntoSVector<Input, N>
         SVector::<T, N>::from_data(arr_storage)
     }
 }
-
-fn newton_iterations<F>(
-    calc_residual: F,
-    x_approx: V2<f64>,
-    num_iterations: usize,
-    tolerance: f64,
-) -> Option<(V2<f64>, f64)>
-where
-    F: Fn(V2<Dual2>) -> V2<Dual2>,
-{
-    let tolerance = tolerance.abs();
-
-    let mut x = x_approx;
-    let mut error = None;
-
-    for _ in 0..num_iterations {
-        let vars = x.into_variables();
-        let x_current = vars.into_s_vector::<Dual2>();
-
-        let residual_dual = calc_residual(x_current);
-
-        let residual = V2::<f64>::from_iterator(
-            residual_dual
-                .iter()
-                .map(|equation| equation.value().to_owned()),
-        );
-
-        error = Some(residual.norm());
-
-        if error.map_or(false, |error| error <= tolerance) {
-            break;
-        }
-
-        let jacobian = M2::<f64>::from_row_iterator(
-            residual_dual
-                .iter()
-                .flat_map(|equation| equation.dual().as_ref().to_owned()),
-        );
-
-        if let Some(increment) = jacobian.qr().solve(&residual) {
-            x -= increment;
-        } else {
-            return None;
-        }
-    }
-    error.map(|error| (x, error))
-}
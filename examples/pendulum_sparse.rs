@@ -2,7 +2,8 @@
     missing_docs,
     clippy::missing_docs_in_private_items,
     clippy::default_numeric_fallback,
-    clippy::indexing_slicing
+    clippy::indexing_slicing,
+    reason = "example"
 )]
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -105,7 +106,7 @@ impl TryFrom<f64> for Fraction {
     }
 }
 
-#[allow(unused)]
+#[allow(unused, reason = "not all schemes are used in the example")]
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
 enum OdeScheme {
@@ -161,7 +162,8 @@ where
     let mut error = None;
 
     for _ in 0..num_iterations {
-        let vars = x.map(|v: f64| v.into_variable());
+        let mut space = sparse::VariableSpace::new();
+        let vars = x.map(|v: f64| space.variable(v));
 
         let x_current = vars.into_s_vector::<Dual2>();
 
@@ -175,18 +177,11 @@ where
 
         error = Some(residual.norm());
 
-        if error.map_or(false, |error| error <= tolerance) {
+        if error.is_some_and(|error| error <= tolerance) {
             break;
         }
 
-        // BUG: I should fix converting sparse dual components to Jacobi matrix.
-        // Surprisingly, this works just fine most of the time.
-        // But I should introduce either dense ordered rows or sparse matrix as resulting storage
-        let jacobian = M2::<f64>::from_row_iterator(
-            residual_dual
-                .iter()
-                .flat_map(|equation| equation.dual().as_ref().values().copied()),
-        );
+        let jacobian = M2::<f64>::from_row_slice(&space.jacobian(residual_dual.as_slice()));
 
         if let Some(increment) = jacobian.qr().solve(&residual) {
             x -= increment;
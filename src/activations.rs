@@ -0,0 +1,102 @@
+//! Differentiable activation functions
+//!
+//!```
+//! use autodj::prelude::single::*;
+//! use autodj::activations::{relu, sigmoid};
+//!
+//! let x = 0.0.into_variable();
+//! assert_eq!(sigmoid(&x).decompose(), (0.5, 0.25));
+//! // subgradient convention at zero
+//! assert_eq!(relu(&x).decompose(), (0.0, 0.0));
+//! ```
+
+use crate::fluid::{Dual, Value};
+use num_traits::{real::Real, One, Zero};
+
+/// Numerically stable logistic function `1/(1+exp(-x))`
+fn logistic<V: Value>(x: V) -> V {
+    if x >= V::zero() {
+        (V::one() + (-x).exp()).recip()
+    } else {
+        let exp = x.exp();
+        exp / (V::one() + exp)
+    }
+}
+
+/// Differentiable logistic sigmoid `1/(1+exp(-x))`
+#[must_use]
+pub fn sigmoid<D: Dual>(x: &D) -> D {
+    x.chain(|&x| {
+        let value = logistic(x);
+        (value, value * (D::Value::one() - value))
+    })
+}
+
+/// Differentiable softplus `ln(1+exp(x))`, a smooth approximation of [`relu`]
+#[must_use]
+pub fn softplus<D: Dual>(x: &D) -> D {
+    x.chain(|&x| {
+        let value = x.max(D::Value::zero()) + (-x.abs()).exp().ln_1p();
+        (value, logistic(x))
+    })
+}
+
+/// Differentiable rectified linear unit `max(x, 0)`.
+///
+/// Derivative at zero is taken to be zero
+#[must_use]
+pub fn relu<D: Dual>(x: &D) -> D {
+    x.chain(|&x| {
+        if x > D::Value::zero() {
+            (x, D::Value::one())
+        } else {
+            (D::Value::zero(), D::Value::zero())
+        }
+    })
+}
+
+/// Differentiable leaky rectified linear unit: `x` for positive `x`, `negative_slope * x` otherwise.
+///
+/// Derivative at zero is taken to be `negative_slope`
+#[must_use]
+pub fn leaky_relu<D: Dual>(x: &D, negative_slope: D::Value) -> D {
+    x.chain(|&x| {
+        if x > D::Value::zero() {
+            (x, D::Value::one())
+        } else {
+            (negative_slope * x, negative_slope)
+        }
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+    use crate::solid::single::IntoVariable;
+
+    #[test]
+    fn sigmoid_saturation() {
+        let (value, deriv) = sigmoid(&(-1000.0_f64).into_variable()).decompose();
+        assert_eq!((value, deriv), (0.0_f64, 0.0_f64));
+        let (value, deriv) = sigmoid(&1000.0_f64.into_variable()).decompose();
+        assert_eq!((value, deriv), (1.0_f64, 0.0_f64));
+    }
+
+    #[test]
+    fn softplus_large() {
+        let (value, deriv) = softplus(&1000.0_f64.into_variable()).decompose();
+        assert_eq!((value, deriv), (1000.0_f64, 1.0_f64));
+        let (value, deriv) = softplus(&0.0_f64.into_variable()).decompose();
+        assert_eq!((value, deriv), (2.0_f64.ln(), 0.5_f64));
+    }
+
+    #[test]
+    fn leaky_relu_branches() {
+        let slope = 0.01_f64;
+        let negative = leaky_relu(&(-2.0_f64).into_variable(), slope).decompose();
+        assert_eq!(negative, (-0.02_f64, slope));
+        let positive = leaky_relu(&2.0_f64.into_variable(), slope).decompose();
+        assert_eq!(positive, (2.0_f64, 1.0_f64));
+    }
+}
@@ -0,0 +1,143 @@
+//! Runtime-configurable assemblies of differentiable functions
+//!
+//! [`DynDualFn`] is object-safe, so contributions of different types
+//! (closures, structs, nested assemblies) are boxed and stored together,
+//! e.g. residuals of a model configured at runtime
+//!
+//!```
+//! use autodj::prelude::vector::*;
+//! use autodj::assembly::Assembly;
+//! use autodj::jacobian;
+//!
+//! let mut model = Assembly::new();
+//! model.push(|x: &[DualF64]| x[0].mul_impl(&x[1]) - 6.0);
+//! for (index, target) in [(0, 2.0), (1, 3.0)] {
+//!     model.push(move |x: &[DualF64]| x[index].clone() - target);
+//! }
+//! assert_eq!(model.len(), 3);
+//!
+//! let jac = jacobian(&model, &[2.0, 3.0]);
+//! assert_eq!(jac.values(), &[0.0, 0.0, 0.0]);
+//! assert_eq!(jac.as_slice(), &[3.0, 2.0, 1.0, 0.0, 0.0, 1.0]);
+//! ```
+#![cfg(feature = "alloc")]
+
+use crate::{
+    fluid::Dual,
+    function::{DynDualFn, VectorDualFn},
+};
+use num_traits::Zero;
+use std::prelude::v1::{Box, Vec};
+
+/// Collection of boxed contributions evaluated over the same variables
+pub struct Assembly<'func, D: Dual> {
+    /// contributions in order of insertion
+    terms: Vec<Box<dyn DynDualFn<D> + 'func>>,
+}
+
+impl<'func, D: Dual> Assembly<'func, D> {
+    /// Empty assembly
+    #[must_use]
+    pub fn new() -> Self {
+        Self { terms: Vec::new() }
+    }
+
+    /// Append a contribution
+    pub fn push<F: DynDualFn<D> + 'func>(&mut self, term: F) {
+        self.terms.push(Box::new(term));
+    }
+
+    /// Number of contributions
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Whether there are no contributions
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Every contribution in order of insertion, e.g. residuals of a system
+    #[must_use]
+    pub fn residuals(&self, variables: &[D]) -> Vec<D> {
+        self.terms.iter().map(|term| term.eval(variables)).collect()
+    }
+
+    /// Sum of contributions, e.g. terms of an objective
+    #[must_use]
+    pub fn total(&self, variables: &[D]) -> D {
+        self.terms
+            .iter()
+            .fold(D::parameter(D::Value::zero()), |mut total, term| {
+                let _ = total.add_assign_impl(&term.eval(variables));
+                total
+            })
+    }
+}
+
+impl<D: Dual> Default for Assembly<'_, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Nested assemblies contribute their [`Assembly::total`]
+impl<D: Dual> DynDualFn<D> for Assembly<'_, D> {
+    fn eval(&self, variables: &[D]) -> D {
+        self.total(variables)
+    }
+}
+
+/// Solvers take assemblies as systems of their [`Assembly::residuals`]
+impl<D: Dual> VectorDualFn<D> for Assembly<'_, D> {
+    fn eval(&self, variables: &[D]) -> Vec<D> {
+        self.residuals(variables)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+    use crate::solid::vector::{DualNumber, IntoVariables};
+    use std::prelude::v1::vec;
+
+    /// Contribution implemented by a struct instead of a closure
+    struct Square(usize);
+
+    impl DynDualFn<DualNumber<f64>> for Square {
+        fn eval(&self, variables: &[DualNumber<f64>]) -> DualNumber<f64> {
+            variables.get(self.0).map_or_else(
+                || DualNumber::parameter(0.0_f64),
+                |variable| variable.mul_impl(variable),
+            )
+        }
+    }
+
+    #[test]
+    fn nested_total() {
+        let mut inner = Assembly::new();
+        inner.push(Square(0));
+        inner.push(Square(1));
+        let mut outer = Assembly::default();
+        outer.push(inner);
+        outer.push(|x: &[DualNumber<f64>]| {
+            x.iter()
+                .fold(DualNumber::parameter(0.0_f64), |acc, xi| acc.add_impl(xi))
+        });
+        let x: Vec<DualNumber<f64>> = vec![1.0_f64, 2.0_f64].into_variables();
+        let (value, grad) = outer.total(&x).decompose();
+        assert_eq!(value, 8.0_f64);
+        assert_eq!(grad.as_ref(), &[3.0_f64, 5.0_f64]);
+    }
+
+    #[test]
+    fn empty_total() {
+        let empty: Assembly<'_, DualNumber<f64>> = Assembly::new();
+        assert!(empty.is_empty());
+        assert!(empty.residuals(&[]).is_empty());
+        assert_eq!(empty.total(&[]).value(), &0.0_f64);
+    }
+}
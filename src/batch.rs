@@ -0,0 +1,119 @@
+//! Evaluation of one function at many points with structure-of-arrays storage
+//!
+//! Values of all points are stored contiguously,
+//! and so are derivatives with respect to each variable,
+//! which suits vectorized post-processing such as Monte Carlo sensitivity statistics
+//!
+//!```
+//! use autodj::prelude::array::*;
+//! use autodj::batch::Batch;
+//!
+//! let points = [[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]];
+//! let batch = Batch::eval(|[x, y]: [DualNumber<f64, 2>; 2]| x * y, &points);
+//!
+//! assert_eq!(batch.values(), &[2.0, 12.0, 30.0]);
+//! assert_eq!(batch.derivatives(0), Some([2.0, 4.0, 6.0].as_slice()));
+//! assert_eq!(batch.derivatives(1), Some([1.0, 3.0, 5.0].as_slice()));
+//! assert_eq!(batch.gradient(1), Some([4.0, 3.0]));
+//! ```
+#![cfg(feature = "alloc")]
+
+use crate::{
+    fluid::{Dual, Value},
+    solid::array::{DualNumber, IntoVariables},
+};
+use std::{
+    array::from_fn,
+    prelude::v1::{vec, Vec},
+};
+
+/// Values and gradients of a function over `N` variables at many points
+#[derive(Clone, Debug, PartialEq)]
+pub struct Batch<V: Value, const N: usize> {
+    /// value at every point
+    values: Vec<V>,
+    /// derivatives with respect to every variable in turn, each over all points
+    derivatives: Vec<V>,
+}
+
+impl<V: Value, const N: usize> Batch<V, N> {
+    /// Evaluate a function at every point
+    pub fn eval<F>(func: F, points: &[[V; N]]) -> Self
+    where
+        F: Fn([DualNumber<V, N>; N]) -> DualNumber<V, N>,
+    {
+        let count = points.len();
+        let mut values = Vec::with_capacity(count);
+        let mut derivatives = vec![V::zero(); count * N];
+        for (index, &point) in points.iter().enumerate() {
+            let (value, grad) = func(point.into_variables()).decompose();
+            values.push(value);
+            for (column, &deriv) in derivatives.chunks_exact_mut(count).zip(grad.as_ref()) {
+                if let Some(elem) = column.get_mut(index) {
+                    *elem = deriv;
+                }
+            }
+        }
+        Self {
+            values,
+            derivatives,
+        }
+    }
+
+    /// Number of points
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether there are no points
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Values at all points
+    #[must_use]
+    pub fn values(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Derivatives with respect to a variable at all points. [`None`] for `variable ≥ N`
+    #[must_use]
+    pub fn derivatives(&self, variable: usize) -> Option<&[V]> {
+        let count = self.len();
+        if variable >= N {
+            return None;
+        }
+        self.derivatives
+            .get(variable * count..(variable + 1) * count)
+    }
+
+    /// Gradient at a point, gathered from strided storage. [`None`] for out-of-range points
+    #[must_use]
+    pub fn gradient(&self, point: usize) -> Option<[V; N]> {
+        let count = self.len();
+        (point < count).then(|| {
+            from_fn(|variable| {
+                self.derivatives
+                    .get(variable * count + point)
+                    .copied()
+                    .unwrap_or_else(V::zero)
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_batch() {
+        let batch = Batch::eval(|[x]: [DualNumber<f64, 1>; 1]| x, &[]);
+        assert!(batch.is_empty());
+        assert_eq!(batch.derivatives(0), Some([].as_slice()));
+        assert_eq!(batch.derivatives(1), None);
+        assert_eq!(batch.gradient(0), None);
+    }
+}
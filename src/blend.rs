@@ -0,0 +1,65 @@
+//! Differentiable blending helpers
+//!
+//!```
+//! use autodj::prelude::array::*;
+//! use autodj::blend::{lerp, smoothstep};
+//!
+//! let [a, b, t]: [DualNumber<f64, 3>; 3] = [1.0, 3.0, 0.25].into_variables();
+//! let blended = lerp(&a, &b, &t);
+//! assert_eq!(blended.value(), &1.5);
+//! assert_eq!(blended.dual().as_ref(), &[0.75, 0.25, 2.0]);
+//!
+//! let ramp = smoothstep(&0.0.into(), &1.0.into(), &t);
+//! assert_eq!(ramp.value(), &0.15625);
+//! ```
+
+use crate::fluid::Dual;
+use num_traits::{One, Zero};
+
+/// Differentiable linear interpolation `a + (b - a)·t`
+#[must_use]
+pub fn lerp<D: Dual>(a: &D, b: &D, t: &D) -> D {
+    a.add_impl(&b.sub_impl(a).mul_impl(t))
+}
+
+/// Differentiable Hermite step `3t² - 2t³`
+/// with `t = (x - edge0) / (edge1 - edge0)` clamped to `[0, 1]`.
+///
+/// Outside of the edges, the result is constant
+#[must_use]
+pub fn smoothstep<D: Dual>(edge0: &D, edge1: &D, x: &D) -> D {
+    let t = x.sub_impl(edge0).div_impl(&edge1.sub_impl(edge0));
+    if t.value() <= &D::Value::zero() {
+        D::parameter(D::Value::zero())
+    } else if t.value() >= &D::Value::one() {
+        D::parameter(D::Value::one())
+    } else {
+        let two = D::Value::one() + D::Value::one();
+        let three = two + D::Value::one();
+        t.chain(|&t| {
+            (
+                t * t * (three - two * t),
+                two * three * t * (D::Value::one() - t),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+    use crate::solid::single::{DualF64, IntoVariable};
+
+    #[test]
+    fn smoothstep_clamped() {
+        let edge0: DualF64 = 1.0_f64.into();
+        let edge1: DualF64 = 2.0_f64.into();
+        let below = smoothstep(&edge0, &edge1, &0.0_f64.into_variable());
+        let above = smoothstep(&edge0, &edge1, &3.0_f64.into_variable());
+        let middle = smoothstep(&edge0, &edge1, &1.5_f64.into_variable());
+        assert_eq!(below.decompose(), (0.0_f64, 0.0_f64));
+        assert_eq!(above.decompose(), (1.0_f64, 0.0_f64));
+        assert_eq!(middle.decompose(), (0.5_f64, 1.5_f64));
+    }
+}
@@ -0,0 +1,244 @@
+//! [`Calibration`] pipeline for fitting named parameters to a residual system
+//!
+//! The pipeline creates independent variables from named parameters,
+//! optionally verifies automatic derivatives against finite differences,
+//! runs [`GaussNewton`] iterations and reports the outcome.
+//!
+//!```
+//! use autodj::prelude::vector::*;
+//! use autodj::calibration::Calibration;
+//!
+//! // fit y = a + b * t
+//! let data = [(0.0, 1.0), (1.0, 3.0), (2.0, 5.0)];
+//! let residual = |p: &[DualF64]| {
+//!     data.iter()
+//!         .map(|&(t, y)| p[0].add_impl(&p[1].mul_impl(&t.into())) - y)
+//!         .collect::<Vec<_>>()
+//! };
+//!
+//! let report = Calibration::new([("a", 0.0), ("b", 0.0)])
+//!     .check_gradients()
+//!     .run(&residual);
+//!
+//! assert!(report.converged());
+//! assert!((report.parameter("b").unwrap() - 2.0).abs() < 1e-12);
+//! println!("{report}");
+//! ```
+#![cfg(feature = "alloc")]
+
+use crate::{
+    fluid::Value,
+    function::VectorDualFn,
+    solid::vector::DualNumber,
+    solver::{evaluate, gauss_newton::GaussNewton, Termination},
+};
+use std::{
+    fmt::{Display, Formatter, Result},
+    prelude::v1::{String, ToString, Vec},
+};
+
+/// Configurable calibration pipeline over named parameters
+#[derive(Clone, Debug, PartialEq)]
+pub struct Calibration<V: Value> {
+    /// names of parameters
+    names: Vec<String>,
+    /// initial guess
+    initial: Vec<V>,
+    /// maximum number of Gauss-Newton iterations
+    max_iterations: usize,
+    /// threshold for both residual norm and step norm
+    tolerance: V,
+    /// Levenberg damping of normal equations
+    damping: V,
+    /// whether to compare derivatives against finite differences
+    check_gradients: bool,
+}
+
+impl<V: Value> Calibration<V> {
+    /// Construct from pairs of parameter names and initial guesses
+    pub fn new<Name, Parameters>(parameters: Parameters) -> Self
+    where
+        Name: ToString,
+        Parameters: IntoIterator<Item = (Name, V)>,
+    {
+        let (names, initial) = parameters
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .unzip();
+        Self {
+            names,
+            initial,
+            max_iterations: 100,
+            tolerance: V::epsilon().sqrt(),
+            damping: V::zero(),
+            check_gradients: false,
+        }
+    }
+
+    /// Set maximum number of iterations
+    #[must_use]
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Set convergence threshold for residual and step norms
+    #[must_use]
+    pub fn tolerance(mut self, tolerance: V) -> Self {
+        self.tolerance = tolerance.abs();
+        self
+    }
+
+    /// Set Levenberg damping of normal equations
+    #[must_use]
+    pub fn damping(mut self, damping: V) -> Self {
+        self.damping = damping.abs();
+        self
+    }
+
+    /// Compare automatic derivatives against central finite differences
+    /// at the initial guess before iterating
+    #[must_use]
+    pub fn check_gradients(mut self) -> Self {
+        self.check_gradients = true;
+        self
+    }
+
+    /// Run the pipeline on a residual system
+    pub fn run<F>(&self, residual: &F) -> CalibrationReport<V>
+    where
+        F: VectorDualFn<DualNumber<V>> + ?Sized,
+    {
+        let gradient_error = self
+            .check_gradients
+            .then(|| gradient_error(residual, &self.initial));
+        let report = GaussNewton::new()
+            .max_iterations(self.max_iterations)
+            .tolerance(self.tolerance)
+            .damping(self.damping)
+            .solve(residual, &self.initial);
+        CalibrationReport {
+            names: self.names.clone(),
+            residual_norm: report.residual_norm(),
+            iterations: report.iterations(),
+            termination: report.termination(),
+            parameters: report.into_solution(),
+            gradient_error,
+        }
+    }
+}
+
+/// Maximum discrepancy between automatic and central finite-difference derivatives,
+/// relative to the magnitude of automatic ones (but not less than absolute)
+fn gradient_error<V, F>(residual: &F, parameters: &[V]) -> V
+where
+    V: Value,
+    F: VectorDualFn<DualNumber<V>> + ?Sized,
+{
+    let columns = parameters.len();
+    let (_, jacobian) = evaluate(residual, parameters, columns);
+    let two = V::one() + V::one();
+    let mut error = V::zero();
+    for column in 0..columns {
+        let shifted = |sign: V| {
+            let mut point = parameters.to_vec();
+            let step = point.get_mut(column).map_or_else(V::zero, |value| {
+                let step = V::epsilon().cbrt() * value.abs().max(V::one());
+                *value += sign * step;
+                step
+            });
+            (evaluate(residual, &point, columns).0, step)
+        };
+        let (forward, step) = shifted(V::one());
+        let (backward, _) = shifted(-V::one());
+        for ((row, &forward), &backward) in jacobian.iter().zip(&forward).zip(&backward) {
+            let automatic = row.get(column).copied().unwrap_or_else(V::zero);
+            let numeric = (forward - backward) / (two * step);
+            error = error.max((automatic - numeric).abs() / automatic.abs().max(V::one()));
+        }
+    }
+    error
+}
+
+/// Outcome of [`Calibration::run`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalibrationReport<V: Value> {
+    /// names of parameters
+    names: Vec<String>,
+    /// calibrated values of parameters
+    parameters: Vec<V>,
+    /// Euclidean norm of final residuals
+    residual_norm: V,
+    /// number of performed iterations
+    iterations: usize,
+    /// reason of stopping iterations
+    termination: Termination,
+    /// result of optional gradient check
+    gradient_error: Option<V>,
+}
+
+impl<V: Value> CalibrationReport<V> {
+    /// Pairs of parameter names and calibrated values
+    pub fn parameters(&self) -> impl Iterator<Item = (&str, V)> {
+        self.names
+            .iter()
+            .map(String::as_str)
+            .zip(self.parameters.iter().copied())
+    }
+
+    /// Calibrated value of a parameter by its name
+    #[must_use]
+    pub fn parameter(&self, name: &str) -> Option<V> {
+        self.parameters()
+            .find_map(|(key, value)| (key == name).then_some(value))
+    }
+
+    /// Euclidean norm of final residuals
+    #[must_use]
+    pub fn residual_norm(&self) -> V {
+        self.residual_norm
+    }
+
+    /// Number of performed iterations
+    #[must_use]
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Reason of stopping iterations
+    #[must_use]
+    pub fn termination(&self) -> Termination {
+        self.termination
+    }
+
+    /// Whether tolerance has been reached
+    #[must_use]
+    pub fn converged(&self) -> bool {
+        self.termination == Termination::Converged
+    }
+
+    /// Maximum relative discrepancy between automatic and finite-difference derivatives
+    /// if [`Calibration::check_gradients`] has been requested
+    #[must_use]
+    pub fn gradient_error(&self) -> Option<V> {
+        self.gradient_error
+    }
+}
+
+impl<V: Value + Display> Display for CalibrationReport<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(
+            f,
+            "{} after {} iterations, |r| = {}",
+            self.termination, self.iterations, self.residual_norm
+        )?;
+        if let Some(error) = self.gradient_error {
+            writeln!(f, "gradient check: max error {error}")?;
+        }
+        let width = self.names.iter().map(String::len).max().unwrap_or_default();
+        for (name, value) in self.parameters() {
+            writeln!(f, "{name:>width$} = {value}")?;
+        }
+        Ok(())
+    }
+}
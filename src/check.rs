@@ -0,0 +1,73 @@
+//! Detection of non-finite values and derivatives where they first appear
+//!
+//! With feature `nan-check` in debug builds, every [`Dual`] operation
+//! checks its result and panics with the operation and its operands
+//! as soon as a value or a derivative becomes NaN or infinite,
+//! instead of letting it propagate silently
+#![cfg(all(feature = "nan-check", debug_assertions))]
+#![allow(
+    clippy::redundant_pub_crate,
+    reason = "conflicts with `unreachable_pub` for private modules"
+)]
+
+use crate::fluid::{Dual, GradIter, Value};
+
+/// Whether a number is neither NaN nor infinite
+fn is_finite<V: Value>(number: V) -> bool {
+    number.abs() <= V::max_value()
+}
+
+/// Panic unless the value and all derivatives of `result` are finite
+#[track_caller]
+pub(crate) fn finite<D: Dual>(result: &D, operation: &str, operands: &[D::Value]) {
+    let value = *result.value();
+    assert!(
+        is_finite(value),
+        "{operation} of {operands:?} gives non-finite value {value:?}"
+    );
+    if let Some((position, deriv)) = result
+        .dual()
+        .derivatives()
+        .map(|(_, deriv)| deriv)
+        .enumerate()
+        .find(|&(_, deriv)| !is_finite(deriv))
+    {
+        panic!(
+            "{operation} of {operands:?} gives non-finite derivative {deriv:?} at position {position}"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fluid::Dual,
+        solid::{array::IntoVariables, single::IntoVariable},
+    };
+
+    #[test]
+    #[should_panic(expected = "division of [1.0, 0.0] gives non-finite value inf")]
+    fn division_by_zero() {
+        let _ = 1.0_f64.into_variable() / 0.0_f64.into_variable();
+    }
+
+    #[test]
+    #[should_panic(expected = "scaling of [1.0, inf] gives non-finite value inf")]
+    fn division_by_zero_constant() {
+        let _ = 1.0_f64.into_variable() / 0.0_f64;
+    }
+
+    #[test]
+    #[should_panic(expected = "powf of [0.0] gives non-finite derivative NaN at position 0")]
+    fn derivative_of_square_root() {
+        let [x, y] = [1.0_f64, 0.0_f64].into_variables();
+        let _ = y.powf(0.5_f64).add_impl(&x);
+    }
+
+    #[test]
+    fn finite_operations() {
+        let [x, y] = [1.0_f64, 2.0_f64].into_variables();
+        let f = x.div_impl(&y).powf(0.5_f64) - y;
+        assert!(f.value().is_finite());
+    }
+}
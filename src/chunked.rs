@@ -0,0 +1,92 @@
+//! Forward-mode gradients of many variables computed a chunk of seed directions at a time
+//!
+//! `func` is re-evaluated once per chunk of `chunk` variables,
+//! the rest being parameters with empty gradients,
+//! so every intermediate holds at most `chunk` derivatives instead of one per variable
+//!
+//!```
+//! use autodj::prelude::vector::*;
+//! use autodj::chunked::chunked_gradient;
+//!
+//! let func = |x: &[DualF64]| x.iter().fold(DualF64::parameter(0.0), |acc, xi| acc + xi.clone() * xi.clone());
+//! let (value, gradient) = chunked_gradient(&func, &[1.0, 2.0, 3.0, 4.0, 5.0], 2);
+//! assert_eq!(value, 55.0);
+//! assert_eq!(gradient, [2.0, 4.0, 6.0, 8.0, 10.0]);
+//! ```
+#![cfg(feature = "alloc")]
+
+use crate::{
+    fluid::{Dual, Value},
+    function::DynDualFn,
+    solid::vector::{DualNumber, IntoVariables},
+};
+use std::prelude::v1::{vec, Vec};
+
+/// Value and dense gradient of a scalar function,
+/// evaluating it once per block of `chunk` variables (at least one)
+pub fn chunked_gradient<V, F>(func: &F, point: &[V], chunk: usize) -> (V, Vec<V>)
+where
+    V: Value,
+    F: DynDualFn<DualNumber<V>> + ?Sized,
+{
+    let columns = point.len();
+    let width = chunk.max(1);
+    let mut value = None;
+    let mut gradient = vec![V::zero(); columns];
+    for (block, derivs) in gradient.chunks_mut(width).enumerate() {
+        let start = block * width;
+        let end = start + derivs.len();
+        let seeds = (0..columns).map(|column| {
+            let mut seed = Vec::new();
+            if (start..end).contains(&column) {
+                seed.resize(end - start, V::zero());
+                if let Some(deriv) = seed.get_mut(column - start) {
+                    *deriv = V::one();
+                }
+            }
+            seed
+        });
+        let (output, grad) = func
+            .eval(&point.to_vec().into_variables_with_seeds(seeds))
+            .decompose();
+        for (elem, &deriv) in derivs.iter_mut().zip(grad.as_ref()) {
+            *elem = deriv;
+        }
+        if value.is_none() {
+            value = Some(output);
+        }
+    }
+    let value = value.unwrap_or_else(|| *func.eval(&point.to_vec().into_variables()).value());
+    (value, gradient)
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_full_gradient() {
+        let func = |x: &[DualNumber<f64>]| {
+            x.windows(2)
+                .fold(DualNumber::parameter(0.0_f64), |acc, pair| match pair {
+                    [left, right] => acc + left.mul_impl(right).sin(),
+                    _ => acc,
+                })
+        };
+        let point: Vec<f64> = (0..23_u32).map(f64::from).collect();
+        let (value, grad) = func(&point.clone().into_variables()).decompose();
+        for chunk in [0, 1, 4, 23, 100] {
+            let (chunked_value, chunked_grad) = chunked_gradient(&func, &point, chunk);
+            assert_eq!(chunked_value, value);
+            assert_eq!(&chunked_grad, grad.as_ref());
+        }
+    }
+
+    #[test]
+    fn no_variables() {
+        let (value, grad) = chunked_gradient(&|_: &[_]| DualNumber::parameter(7.0_f64), &[], 3);
+        assert_eq!(value, 7.0_f64);
+        assert!(grad.is_empty());
+    }
+}
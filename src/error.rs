@@ -0,0 +1,156 @@
+//! Errors of fallible APIs across the crate
+//!
+//! Every fallible API returns its own precise error type,
+//! while [`Error`] gathers all of them, so `?` combines different APIs
+//!
+//!```
+//! use autodj::prelude::single::*;
+//! use autodj::error::Error;
+//! use autodj::fluid::DomainError;
+//!
+//! fn entropy(p: f64) -> Result<DualF64, Error> {
+//!     let p = p.into_variable();
+//!     let ln = p.try_ln()?;
+//!     Ok(-(p * ln))
+//! }
+//!
+//! assert_eq!(entropy(1.0).map(|h| h.decompose()), Ok((-0.0, -1.0)));
+//! assert_eq!(entropy(0.0), Err(Error::Domain(DomainError { function: "ln" })));
+//! ```
+
+use crate::{
+    fluid::DomainError,
+    solid::{fixed::CapacityError, ParseDualError},
+};
+use std::fmt::{Display, Formatter, Result};
+
+/// Lengths of gradients of the operands differ, so they belong to different sets of variables
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DimensionMismatch {
+    /// length of the left operand
+    pub left: usize,
+    /// length of the right operand
+    pub right: usize,
+}
+
+impl Display for DimensionMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "gradient lengths {} and {} do not match",
+            self.left, self.right
+        )
+    }
+}
+
+/// Reason why a table of knots and values cannot be interpolated
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TableError {
+    /// fewer than two knots
+    TooFewKnots,
+    /// numbers of knots and values differ
+    LengthMismatch {
+        /// number of knots
+        knots: usize,
+        /// number of values
+        values: usize,
+    },
+    /// knots do not strictly increase
+    Unordered,
+}
+
+impl Display for TableError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::TooFewKnots => write!(f, "table needs at least two knots"),
+            Self::LengthMismatch { knots, values } => {
+                write!(f, "{knots} knots do not match {values} values")
+            }
+            Self::Unordered => write!(f, "knots do not strictly increase"),
+        }
+    }
+}
+
+/// Any error of the crate
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Error {
+    /// gradients of different sets of variables are combined
+    DimensionMismatch(DimensionMismatch),
+    /// argument is outside the domain of a function
+    Domain(DomainError),
+    /// fixed-capacity gradient is full
+    Capacity(CapacityError),
+    /// dual number literal is malformed
+    Parse(ParseDualError),
+    /// interpolation table is malformed
+    Table(TableError),
+    /// iteration limit is exhausted before reaching tolerance
+    Convergence,
+    /// Jacobian could not be inverted
+    Singular,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::DimensionMismatch(error) => error.fmt(f),
+            Self::Domain(error) => error.fmt(f),
+            Self::Capacity(error) => error.fmt(f),
+            Self::Parse(error) => error.fmt(f),
+            Self::Table(error) => error.fmt(f),
+            Self::Convergence => write!(f, "iterations did not converge"),
+            Self::Singular => write!(f, "singular Jacobian"),
+        }
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::DimensionMismatch(error) => Some(error),
+            Self::Domain(error) => Some(error),
+            Self::Capacity(error) => Some(error),
+            Self::Parse(error) => Some(error),
+            Self::Table(error) => Some(error),
+            Self::Convergence | Self::Singular => None,
+        }
+    }
+}
+
+impl core::error::Error for DimensionMismatch {}
+impl core::error::Error for DomainError {}
+impl core::error::Error for CapacityError {}
+impl core::error::Error for ParseDualError {}
+impl core::error::Error for TableError {}
+
+impl From<DimensionMismatch> for Error {
+    fn from(error: DimensionMismatch) -> Self {
+        Self::DimensionMismatch(error)
+    }
+}
+
+impl From<DomainError> for Error {
+    fn from(error: DomainError) -> Self {
+        Self::Domain(error)
+    }
+}
+
+impl From<CapacityError> for Error {
+    fn from(error: CapacityError) -> Self {
+        Self::Capacity(error)
+    }
+}
+
+impl From<ParseDualError> for Error {
+    fn from(error: ParseDualError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+impl From<TableError> for Error {
+    fn from(error: TableError) -> Self {
+        Self::Table(error)
+    }
+}
@@ -0,0 +1,235 @@
+//! One-call evaluation of a function together with its gradient
+//!
+//! Variables are seeded internally, so only the function has to be written over dual numbers
+//!
+//!```
+//! use autodj::prelude::vector::*;
+//! use autodj::eval::value_and_grad;
+//!
+//! let (value, grad) = value_and_grad(&|x: &[DualF64]| x[0].mul_impl(&x[1]).sin(), &[1.0, 0.0]);
+//! assert_eq!(value, 0.0);
+//! assert_eq!(grad, [0.0, 1.0]);
+//! ```
+#![cfg(feature = "alloc")]
+
+use crate::{
+    fluid::{Dual, Value},
+    function::{DynDualFn, VectorDualFn},
+    solid::{array, vector},
+};
+use std::prelude::v1::Vec;
+
+/// Value and gradient of a function over a slice of variables
+pub fn value_and_grad<V, F>(func: &F, point: &[V]) -> (V, Vec<V>)
+where
+    V: Value,
+    F: DynDualFn<vector::DualNumber<V>> + ?Sized,
+{
+    use vector::IntoVariables;
+
+    let (value, grad) = func.eval(&point.to_vec().into_variables()).decompose();
+    let mut grad: Vec<V> = grad.as_ref().clone();
+    grad.resize(point.len(), V::zero());
+    (value, grad)
+}
+
+/// Turn a function over dual numbers into a plain gradient callback,
+/// e.g. for optimizers expecting `Fn(&[f64]) -> Vec<f64>`
+///```
+/// use autodj::prelude::vector::*;
+/// use autodj::eval::{grad_fn, value_and_grad_fn};
+///
+/// let f = |x: &[DualF64]| x[0].mul_impl(&x[1]);
+/// let gradient = grad_fn(f);
+/// assert_eq!(gradient(&[2.0, 3.0]), [3.0, 2.0]);
+///
+/// let both = value_and_grad_fn(f);
+/// assert_eq!(both(&[2.0, 3.0]), (6.0, vec![3.0, 2.0]));
+/// ```
+pub fn grad_fn<V, F>(func: F) -> impl Fn(&[V]) -> Vec<V>
+where
+    V: Value,
+    F: DynDualFn<vector::DualNumber<V>>,
+{
+    move |point| value_and_grad(&|x: &[_]| func.eval(x), point).1
+}
+
+/// Turn a function over dual numbers into a plain callback returning value and gradient
+pub fn value_and_grad_fn<V, F>(func: F) -> impl Fn(&[V]) -> (V, Vec<V>)
+where
+    V: Value,
+    F: DynDualFn<vector::DualNumber<V>>,
+{
+    move |point| value_and_grad(&|x: &[_]| func.eval(x), point)
+}
+
+/// Value and gradient of a function over an array of variables
+///```
+/// use autodj::prelude::array::*;
+/// use autodj::eval::value_and_grad_array;
+///
+/// let (value, grad) = value_and_grad_array(|[x, y]| x * y + x, [2.0, 3.0]);
+/// assert_eq!(value, 8.0);
+/// assert_eq!(grad, [4.0, 2.0]);
+/// ```
+pub fn value_and_grad_array<V, F, const N: usize>(func: F, point: [V; N]) -> (V, [V; N])
+where
+    V: Value,
+    F: Fn([array::DualNumber<V, N>; N]) -> array::DualNumber<V, N>,
+{
+    use array::IntoVariables;
+
+    let (value, grad) = func(point.into_variables()).decompose();
+    (value, *grad.as_ref())
+}
+
+/// Value and gradient of a function over a [`nalgebra::DVector`] of variables
+///```
+/// use autodj::prelude::vector::*;
+/// use autodj::eval::value_and_grad_nalgebra;
+/// use nalgebra::{dvector, DVector};
+///
+/// let norm_squared = |x: &DVector<DualF64>| x.iter().map(|xi| xi.mul_impl(xi)).sum();
+/// let (value, grad) = value_and_grad_nalgebra(norm_squared, &dvector![1.0, 2.0]);
+/// assert_eq!(value, 5.0);
+/// assert_eq!(grad, dvector![2.0, 4.0]);
+/// ```
+#[cfg(feature = "nalgebra")]
+pub fn value_and_grad_nalgebra<V, F>(
+    func: F,
+    point: &nalgebra::DVector<V>,
+) -> (V, nalgebra::DVector<V>)
+where
+    V: Value + nalgebra::Scalar,
+    F: Fn(&nalgebra::DVector<vector::DualNumber<V>>) -> vector::DualNumber<V>,
+{
+    let (value, grad) = value_and_grad(
+        &|x: &[vector::DualNumber<V>]| func(&nalgebra::DVector::from_column_slice(x)),
+        point.as_slice(),
+    );
+    (value, nalgebra::DVector::from_vec(grad))
+}
+
+/// Dense row-major Jacobian of a vector function together with its values
+#[derive(Clone, Debug, PartialEq)]
+pub struct Jacobian<V: Value> {
+    /// value of every output
+    values: Vec<V>,
+    /// number of variables
+    columns: usize,
+    /// derivatives, a row per output
+    entries: Vec<V>,
+}
+
+impl<V: Value> Jacobian<V> {
+    /// Values of outputs
+    #[must_use]
+    pub fn values(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Number of outputs
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Number of variables
+    #[must_use]
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// All derivatives in row-major order
+    #[must_use]
+    pub fn as_slice(&self) -> &[V] {
+        &self.entries
+    }
+
+    /// Derivatives of an output. [`None`] for out-of-range rows
+    #[must_use]
+    pub fn row(&self, row: usize) -> Option<&[V]> {
+        self.entries
+            .get(row * self.columns..(row + 1) * self.columns)
+    }
+
+    /// Derivative of an output with respect to a variable. [`None`] out of range
+    #[must_use]
+    pub fn get(&self, row: usize, column: usize) -> Option<V> {
+        (column < self.columns)
+            .then(|| self.entries.get(row * self.columns + column).copied())
+            .flatten()
+    }
+
+    /// Convert into [`nalgebra::DMatrix`]
+    #[cfg(feature = "nalgebra")]
+    #[must_use]
+    pub fn to_matrix(&self) -> nalgebra::DMatrix<V>
+    where
+        V: nalgebra::Scalar,
+    {
+        nalgebra::DMatrix::from_row_slice(self.rows(), self.columns, &self.entries)
+    }
+}
+
+/// Values and Jacobian of a function returning several dual numbers.
+/// Rows follow the order of outputs
+///```
+/// use autodj::prelude::vector::*;
+/// use autodj::jacobian;
+///
+/// let jac = jacobian(&|x: &[DualF64]| vec![x[0].mul_impl(&x[1]), x[1].clone()], &[2.0, 3.0]);
+/// assert_eq!(jac.values(), &[6.0, 3.0]);
+/// assert_eq!(jac.row(0), Some([3.0, 2.0].as_slice()));
+/// assert_eq!(jac.get(1, 1), Some(1.0));
+/// assert_eq!(jac.as_slice(), &[3.0, 2.0, 0.0, 1.0]);
+/// ```
+pub fn jacobian<V, F>(func: &F, point: &[V]) -> Jacobian<V>
+where
+    V: Value,
+    F: VectorDualFn<vector::DualNumber<V>> + ?Sized,
+{
+    use vector::IntoVariables;
+
+    let columns = point.len();
+    let mut values = Vec::new();
+    let mut entries = Vec::new();
+    for output in func.eval(&point.to_vec().into_variables()) {
+        let (value, grad) = output.decompose();
+        values.push(value);
+        let start = entries.len();
+        entries.extend(grad.as_ref().iter().take(columns));
+        entries.resize(start + columns, V::zero());
+    }
+    Jacobian {
+        values,
+        columns,
+        entries,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jacobian_bounds() {
+        let jac = jacobian(&<[vector::DualNumber<f64>]>::to_vec, &[1.0_f64, 2.0_f64]);
+        assert_eq!(jac.rows(), 2);
+        assert_eq!(jac.columns(), 2);
+        assert_eq!(jac.get(0, 2), None);
+        assert_eq!(jac.get(2, 0), None);
+        assert_eq!(jac.row(2), None);
+    }
+
+    #[test]
+    fn gradient_of_constant() {
+        let (value, grad) = value_and_grad(
+            &|_: &[_]| vector::DualNumber::parameter(3.0_f64),
+            &[1.0_f64; 4],
+        );
+        assert_eq!(value, 3.0_f64);
+        assert_eq!(grad, [0.0_f64; 4]);
+    }
+}
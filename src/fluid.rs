@@ -1,8 +1,10 @@
 //! [`Dual`] trait as behavior definition
 
-use num_traits::{real::Real, One, Zero};
+use num_traits::{float::TotalOrder, real::Real, Float, One, ToPrimitive, Zero};
 use std::{
+    cmp::Ordering,
     fmt::{Debug, Display, Formatter, LowerExp, Result},
+    hash::{Hash, Hasher},
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
@@ -10,11 +12,37 @@ use std::{
 pub trait Value: Real + AddAssign + MulAssign + SubAssign + Debug {}
 impl<R> Value for R where R: Real + AddAssign + MulAssign + SubAssign + Debug {}
 
+/// Check the result of an operation given its operands
+/// with feature `nan-check` in debug builds, see [`crate::check`]
+macro_rules! check_finite {
+    ($result:expr, $operation:expr, $($operand:expr),+) => {
+        #[cfg(all(feature = "nan-check", debug_assertions))]
+        crate::check::finite($result, $operation, &[$($operand),+]);
+        // the operation name is only reported by the check
+        #[cfg(not(all(feature = "nan-check", debug_assertions)))]
+        let _ = $operation;
+    };
+}
+pub(crate) use check_finite;
+
+/// Derivatives inspected by feature `nan-check`, which therefore requires [`GradIter`]
+#[cfg(feature = "nan-check")]
+pub trait FiniteCheck<V: Value>: GradIter<V> {}
+#[cfg(feature = "nan-check")]
+impl<V: Value, G: GradIter<V>> FiniteCheck<V> for G {}
+
+/// Derivatives inspected by feature `nan-check`, no requirements without it
+#[cfg(not(feature = "nan-check"))]
+pub trait FiniteCheck<V: Value> {}
+#[cfg(not(feature = "nan-check"))]
+impl<V: Value, G> FiniteCheck<V> for G {}
+
 /// Derivatives
 pub trait Grad<V: Value>
 where
     Self: Clone
         + AddAssign
+        + FiniteCheck<V>
         + Neg<Output = Self>
         + MulAssign<V>
         + Mul<V, Output = Self>
@@ -22,17 +50,64 @@ where
         + PartialEq
         + Zero,
 {
+    /// Derivatives divided by a value, [`None`] for zero or NaN divisors
+    ///```
+    /// use autodj::prelude::array::*;
+    ///
+    /// let [x, y] = [1.0, 2.0].into_variables();
+    /// let grad = (x * y).decompose().1;
+    /// assert_eq!(grad.checked_div(2.0).map(|grad| grad.as_ref().to_owned()), Some([1.0, 0.5]));
+    /// assert_eq!(grad.checked_div(0.0), None);
+    /// ```
+    #[must_use]
+    fn checked_div(self, rhs: V) -> Option<Self> {
+        (rhs < V::zero() || rhs > V::zero()).then(|| self * rhs.recip())
+    }
+
+    /// Add `alpha * x` in place, e.g. for compound assignments of dual numbers.
+    /// Built-in gradients fuse it without temporaries, so compound assignments never allocate
+    fn axpy(&mut self, alpha: V, x: &Self) {
+        *self += x.clone() * alpha;
+    }
 }
-impl<V: Value, G> Grad<V> for G where
-    G: Clone
-        + AddAssign
-        + MulAssign<V>
-        + Mul<V, Output = Self>
-        + Neg<Output = Self>
-        + PartialEq
-        + PartialOrd
-        + Zero
-{
+impl<V: Value> Grad<V> for V {}
+
+/// Iteration over derivatives regardless of gradient storage,
+/// e.g. to assemble Jacobians from different [`Grad`] implementors
+///```
+/// use autodj::prelude::array::*;
+///
+/// let [x, y] = [1.0, 2.0].into_variables();
+/// let f = x * y;
+/// let row: Vec<(usize, f64)> = f.dual().derivatives().collect();
+/// assert_eq!(row, [(0, 2.0), (1, 1.0)]);
+/// assert_eq!(f.dual().dim(), Some(2));
+/// ```
+pub trait GradIter<V: Value> {
+    /// Identifier of a derivative
+    type Key;
+
+    /// Pairs of keys and derivatives. Order is implementation-defined
+    fn derivatives(&self) -> impl Iterator<Item = (Self::Key, V)> + '_;
+
+    /// Number of derivatives held by dense storage,
+    /// e.g. to size Jacobians. [`None`] for sparse storage
+    fn dim(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// Derivative of a single variable
+impl<V: Value> GradIter<V> for V {
+    type Key = usize;
+
+    fn derivatives(&self) -> impl Iterator<Item = (usize, V)> + '_ {
+        std::iter::once((0, *self))
+    }
+
+    fn dim(&self) -> Option<usize> {
+        Some(1)
+    }
 }
 
 // FIXME: replace some trait bounds with another bounded traits like `NumOps` to write less code
@@ -91,47 +166,143 @@ where
     /// [`Fn(f64) -> (f64, f64)`] evaluates both function and its derivative
     #[must_use]
     fn chain(&self, func: impl Fn(&Self::Value) -> (Self::Value, Self::Value)) -> Self {
+        self.chain_named("chain", func)
+    }
+
+    /// [`Dual::chain`] reporting `operation` as its name, e.g. by feature `nan-check`
+    #[must_use]
+    fn chain_named(
+        &self,
+        operation: &'static str,
+        func: impl Fn(&Self::Value) -> (Self::Value, Self::Value),
+    ) -> Self {
+        #[cfg(feature = "stats")]
+        crate::stats::record_chain();
         let (f, df) = func(self.value());
         let dual_new = self.dual().clone() * df;
-        Self::new(f, dual_new)
+        let output = Self::new(f, dual_new);
+        check_finite!(&output, operation, *self.value());
+        output
     }
 
-    /// Differentiable [`Real::powf`]
+    /// Binary counterpart of [`Dual::chain`]: `func` evaluates `f(x, y)`,
+    /// while `df_dx` and `df_dy` evaluate its partial derivatives
+    ///```
+    /// use autodj::prelude::array::*;
+    ///
+    /// let [y, x]: [DualNumber<f64, 2>; 2] = [1.0, 1.0].into_variables();
+    /// let atan2 = y.zip_with(
+    ///     &x,
+    ///     |y, x| y.atan2(*x),
+    ///     |y, x| *x / (x * x + y * y),
+    ///     |y, x| -*y / (x * x + y * y),
+    /// );
+    /// assert_eq!(atan2.value(), &core::f64::consts::FRAC_PI_4);
+    /// assert_eq!(atan2.dual().as_ref(), &[0.5, -0.5]);
+    /// ```
+    #[must_use]
+    fn zip_with<F, Dx, Dy>(&self, rhs: &Self, func: F, df_dx: Dx, df_dy: Dy) -> Self
+    where
+        F: Fn(&Self::Value, &Self::Value) -> Self::Value,
+        Dx: Fn(&Self::Value, &Self::Value) -> Self::Value,
+        Dy: Fn(&Self::Value, &Self::Value) -> Self::Value,
+    {
+        self.zip_with_named("zip_with", rhs, func, df_dx, df_dy)
+    }
+
+    /// [`Dual::zip_with`] reporting `operation` as its name, e.g. by feature `nan-check`
+    #[must_use]
+    fn zip_with_named<F, Dx, Dy>(
+        &self,
+        operation: &'static str,
+        rhs: &Self,
+        func: F,
+        df_dx: Dx,
+        df_dy: Dy,
+    ) -> Self
+    where
+        F: Fn(&Self::Value, &Self::Value) -> Self::Value,
+        Dx: Fn(&Self::Value, &Self::Value) -> Self::Value,
+        Dy: Fn(&Self::Value, &Self::Value) -> Self::Value,
+    {
+        #[cfg(feature = "stats")]
+        crate::stats::record_chain();
+        let (x, y) = (self.value(), rhs.value());
+        let mut dual_new = self.dual().clone() * df_dx(x, y);
+        dual_new += rhs.dual().clone() * df_dy(x, y);
+        let output = Self::new(func(x, y), dual_new);
+        check_finite!(&output, operation, *x, *y);
+        output
+    }
+
+    /// Differentiable [`Real::powf`].
+    /// `x^(exp-1)` is evaluated once (by [`Real::powi`] for integral exponents),
+    /// and the value is derived from it unless `x^(exp-1)` is zero or not finite
+    ///```
+    /// use autodj::prelude::single::*;
+    ///
+    /// assert_eq!(3.0.into_variable().powf(2.0).decompose(), (9.0, 6.0));
+    /// assert_eq!(0.0.into_variable().powf(2.0).decompose(), (0.0, 0.0));
+    /// # #[cfg(not(feature = "nan-check"))]
+    /// assert_eq!(0.0.into_variable().powf(0.5).value(), &0.0);
+    /// ```
     #[must_use]
     #[inline]
     fn powf(&self, exp: Self::Value) -> Self {
-        self.chain(|x: &Self::Value| (x.powf(exp), x.powf(exp - Self::Value::one()) * exp))
+        let integral = exp.fract().is_zero().then(|| exp.to_i32()).flatten();
+        let lowered_integral = integral.and_then(|power| power.checked_sub(1));
+        self.chain_named("powf", |x: &Self::Value| {
+            let lowered = lowered_integral
+                .map_or_else(|| x.powf(exp - Self::Value::one()), |power| x.powi(power));
+            let value = if lowered.abs() <= Self::Value::max_value() && !lowered.is_zero() {
+                lowered * *x
+            } else {
+                integral.map_or_else(|| x.powf(exp), |power| x.powi(power))
+            };
+            let deriv = if exp.is_zero() {
+                Self::Value::zero()
+            } else {
+                lowered * exp
+            };
+            (value, deriv)
+        })
     }
 
     /// Differentiable [`Real::sin`]
     #[must_use]
     fn sin(&self) -> Self {
-        self.sin_cos().0 // TODO: check if the other tuple member is optimized out
+        self.chain_named("sin", |x| x.sin_cos())
     }
 
     /// Differentiable [`Real::cos`]
     #[must_use]
     fn cos(&self) -> Self {
-        self.sin_cos().1 // TODO: check if the other tuple member is optimized out
+        self.chain_named("cos", |x| {
+            let (sin, cos) = x.sin_cos();
+            (cos, -sin)
+        })
     }
 
     /// Differentiable [`Real::sin_cos`]
     fn sin_cos(&self) -> (Self, Self) {
         let (sin, cos) = self.value().sin_cos();
-        (self.chain(|_| (sin, cos)), self.chain(|_| (cos, -sin)))
+        (
+            self.chain_named("sin", |_| (sin, cos)),
+            self.chain_named("cos", |_| (cos, -sin)),
+        )
     }
 
     /// Differentiable [`Real::exp`]
     #[must_use]
     fn exp(&self) -> Self {
         let real = self.value().exp();
-        self.chain(|_| (real, real))
+        self.chain_named("exp", |_| (real, real))
     }
 
     /// Differentiable [`Real::ln`]
     #[must_use]
     fn ln(&self) -> Self {
-        self.chain(|x| (x.ln(), x.recip()))
+        self.chain_named("ln", |x| (x.ln(), x.recip()))
     }
 
     /// Differentiable reciprocal of [`Dual`]
@@ -143,13 +314,87 @@ where
     /// Differentiable [`Real::abs`]
     #[must_use]
     fn abs(&self) -> Self {
-        self.chain(|x| (x.abs(), x.signum()))
+        self.chain_named("abs", |x| (x.abs(), x.signum()))
     }
 
     /// Differentiable [`Real::signum`]
     #[must_use]
     fn signum(&self) -> Self {
-        self.chain(|x| (x.signum(), Self::Value::zero()))
+        self.chain_named("signum", |x| (x.signum(), Self::Value::zero()))
+    }
+
+    /// [`Dual::ln`] of positive values only, since neither `ln` nor its derivative
+    /// are finite elsewhere, e.g. for solvers to backtrack instead of propagating NaN
+    /// # Errors
+    /// [`DomainError`] unless the value is positive
+    ///```
+    /// use autodj::prelude::single::*;
+    /// use autodj::fluid::DomainError;
+    ///
+    /// assert_eq!(1.0.into_variable().try_ln().map(|f| f.decompose()), Ok((0.0, 1.0)));
+    /// assert_eq!(0.0.into_variable().try_ln(), Err(DomainError { function: "ln" }));
+    /// ```
+    fn try_ln(&self) -> core::result::Result<Self, DomainError> {
+        DomainError::check("ln", *self.value() > Self::Value::zero())?;
+        Ok(self.ln())
+    }
+
+    /// Differentiable [`Real::sqrt`] of positive values only,
+    /// since the derivative is infinite at zero
+    /// # Errors
+    /// [`DomainError`] unless the value is positive
+    fn try_sqrt(&self) -> core::result::Result<Self, DomainError> {
+        DomainError::check("sqrt", *self.value() > Self::Value::zero())?;
+        Ok(self.chain_named("sqrt", |x| {
+            let sqrt = x.sqrt();
+            (sqrt, half::<Self::Value>() / sqrt)
+        }))
+    }
+
+    /// [`Dual::powf`] with finite exponents of positive values,
+    /// of zero for exponents not below one, and of negative values for integral exponents
+    /// # Errors
+    /// [`DomainError`] for other values and exponents
+    fn try_powf(&self, exp: Self::Value) -> core::result::Result<Self, DomainError> {
+        let x = *self.value();
+        let valid = exp.abs() <= Self::Value::max_value()
+            && (x > Self::Value::zero()
+                || (x.is_zero() && exp >= Self::Value::one())
+                || (x < Self::Value::zero() && exp.fract().is_zero()));
+        DomainError::check("powf", valid)?;
+        Ok(self.powf(exp))
+    }
+
+    /// [`Dual::recip`] of nonzero values only
+    /// # Errors
+    /// [`DomainError`] for zero or NaN values
+    fn try_recip(&self) -> core::result::Result<Self, DomainError> {
+        let x = *self.value();
+        DomainError::check("recip", x < Self::Value::zero() || x > Self::Value::zero())?;
+        Ok(self.recip())
+    }
+
+    /// [`Dual::div_impl`] by nonzero values only, since derivatives of a quotient
+    /// are divided by the squared value of `rhs`
+    /// # Errors
+    /// [`DomainError`] if the value of `rhs` is zero or NaN
+    fn try_div(&self, rhs: &Self) -> core::result::Result<Self, DomainError> {
+        let y = *rhs.value();
+        DomainError::check("div", y < Self::Value::zero() || y > Self::Value::zero())?;
+        Ok(self.div_impl(rhs))
+    }
+
+    /// [`Dual::try_div`] discarding the error
+    ///```
+    /// use autodj::prelude::array::*;
+    ///
+    /// let [x, y] = [1.0, 2.0].into_variables();
+    /// assert_eq!(x.checked_div(&y).map(|q| q.dual().as_ref().to_owned()), Some([0.5, -0.25]));
+    /// assert_eq!(x.checked_div(&(y - 2.0)), None);
+    /// ```
+    #[must_use]
+    fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        self.try_div(rhs).ok()
     }
 
     /// To further implement [`std::ops::Add`] for structs
@@ -186,34 +431,146 @@ where
 
     /// To further implement [`std::ops::AddAssign`] for structs
     fn add_assign_impl(&mut self, rhs: &Self) -> &mut Self {
+        #[cfg(feature = "stats")]
+        crate::stats::record_operation();
+        #[cfg(all(feature = "nan-check", debug_assertions))]
+        let lhs = *self.value();
         *self.value_mut() += *rhs.value();
-        *self.dual_mut() += rhs.dual().clone();
+        self.dual_mut().axpy(Self::Value::one(), rhs.dual());
+        check_finite!(self, "addition", lhs, *rhs.value());
         self
     }
 
     /// To further implement [`std::ops::MulAssign`] for structs
     fn mul_assign_impl(&mut self, rhs: &Self) -> &mut Self {
+        #[cfg(feature = "stats")]
+        crate::stats::record_operation();
         let value_local = *self.value(); // preserve original value
         *self.value_mut() *= *rhs.value();
         *self.dual_mut() *= *rhs.value();
-        *self.dual_mut() += rhs.dual().clone() * value_local;
+        self.dual_mut().axpy(value_local, rhs.dual());
+        check_finite!(self, "multiplication", value_local, *rhs.value());
         self
     }
 
     /// To further implement [`std::ops::SubAssign`] for structs
     fn sub_assign_impl(&mut self, rhs: &Self) -> &mut Self {
-        self.add_assign_impl(&rhs.neg_impl())
+        #[cfg(feature = "stats")]
+        crate::stats::record_operation();
+        #[cfg(all(feature = "nan-check", debug_assertions))]
+        let lhs = *self.value();
+        *self.value_mut() -= *rhs.value();
+        self.dual_mut().axpy(-Self::Value::one(), rhs.dual());
+        check_finite!(self, "subtraction", lhs, *rhs.value());
+        self
     }
 
-    /// To further implement [`std::ops::DivAssign`] for structs
+    /// To further implement [`std::ops::DivAssign`] for structs.
+    /// `(u/v)' = u'/v - (u/v)·v'/v`
     fn div_assign_impl(&mut self, rhs: &Self) -> &mut Self {
-        self.mul_assign_impl(&rhs.recip())
+        #[cfg(feature = "stats")]
+        crate::stats::record_operation();
+        #[cfg(all(feature = "nan-check", debug_assertions))]
+        let lhs = *self.value();
+        let recip = rhs.value().recip();
+        let quotient = *self.value() * recip;
+        *self.value_mut() = quotient;
+        *self.dual_mut() *= recip;
+        self.dual_mut().axpy(-quotient * recip, rhs.dual());
+        check_finite!(self, "division", lhs, *rhs.value());
+        self
     }
 
     /// To further implement [`std::ops::Neg`] for structs
     #[must_use]
     fn neg_impl(&self) -> Self {
-        Self::new(self.value().neg(), self.dual().clone().neg())
+        self.clone().neg_owned()
+    }
+
+    /// Consuming counterpart of [`Dual::add_assign_impl`]: gradient of `rhs` is moved, not cloned
+    fn add_assign_owned(&mut self, rhs: Self) -> &mut Self {
+        #[cfg(feature = "stats")]
+        crate::stats::record_operation();
+        let (value, grad) = rhs.decompose();
+        #[cfg(all(feature = "nan-check", debug_assertions))]
+        let lhs = *self.value();
+        *self.value_mut() += value;
+        *self.dual_mut() += grad;
+        check_finite!(self, "addition", lhs, value);
+        self
+    }
+
+    /// Consuming counterpart of [`Dual::mul_assign_impl`]: gradient of `rhs` is scaled in place
+    fn mul_assign_owned(&mut self, rhs: Self) -> &mut Self {
+        #[cfg(feature = "stats")]
+        crate::stats::record_operation();
+        let (value, mut grad) = rhs.decompose();
+        #[cfg(all(feature = "nan-check", debug_assertions))]
+        let lhs = *self.value();
+        grad *= *self.value();
+        *self.value_mut() *= value;
+        *self.dual_mut() *= value;
+        *self.dual_mut() += grad;
+        check_finite!(self, "multiplication", lhs, value);
+        self
+    }
+
+    /// Consuming counterpart of [`Dual::neg_impl`]
+    #[must_use]
+    fn neg_owned(self) -> Self {
+        #[cfg(feature = "stats")]
+        crate::stats::record_operation();
+        let (value, grad) = self.decompose();
+        let output = Self::new(value.neg(), grad.neg());
+        check_finite!(&output, "negation", value);
+        output
+    }
+
+    /// Derivative with respect to the `index`-th variable for any index-keyed gradient.
+    /// [`None`] if it is not stored, e.g. beyond the length of a dynamic gradient
+    ///```
+    /// # #[cfg(feature = "alloc")] {
+    /// use autodj::prelude::vector::*;
+    ///
+    /// let x: Vec<DualF64> = vec![1.0, 2.0, 3.0].into_variables();
+    /// let f = x[0].mul_impl(&x[2]);
+    /// assert_eq!(f.partial(0), Some(3.0));
+    /// assert_eq!(f.partial(1), Some(0.0));
+    /// assert_eq!(f.partial(3), None);
+    /// # }
+    /// ```
+    fn partial(&self, index: usize) -> Option<Self::Value>
+    where
+        Self::Grad: GradIter<Self::Value, Key = usize>,
+    {
+        self.dual()
+            .derivatives()
+            .find_map(|(key, deriv)| (key == index).then_some(deriv))
+    }
+
+    /// Display with custom [`DisplayOptions`](crate::format::DisplayOptions),
+    /// e.g. without the `∆` glyph
+    ///```
+    /// use autodj::prelude::single::*;
+    /// use autodj::format::DisplayOptions;
+    ///
+    /// let f = 2.0.into_variable().powf(3.0);
+    /// let ascii = DisplayOptions::new().symbol("d");
+    /// assert_eq!(f.format_with(&ascii).to_string(), "8+[12]d");
+    /// ```
+    fn format_with<'a>(
+        &'a self,
+        options: &'a crate::format::DisplayOptions,
+    ) -> crate::format::Formatted<'a, Self> {
+        crate::format::Formatted::new(self, options)
+    }
+
+    /// Total order of values, ignoring derivatives. See [`TotalOrder::total_cmp`]
+    fn total_cmp(&self, other: &Self) -> Ordering
+    where
+        Self::Value: TotalOrder,
+    {
+        self.value().total_cmp(other.value())
     }
 
     /// Evaluate function over a single dual number
@@ -222,14 +579,164 @@ where
     }
 }
 
-/// Fluid implementation of [`Display`] to use with solid structs
+/// Pass results of a model stage to the next one, so pipelines read left to right
+///```
+/// # #[cfg(feature = "alloc")] {
+/// use autodj::prelude::vector::*;
+///
+/// let variables: DualVariables<f64> = vec![1.0, 2.0].into();
+/// let energy = variables
+///     .eval(|x| x.iter().map(|xi| xi.mul_impl(xi)).collect::<Vec<_>>())
+///     .then(|squares| squares.into_iter().sum::<DualF64>())
+///     .then(|sum| sum.exp());
+/// assert_eq!(energy.value(), &5.0_f64.exp());
+/// assert_eq!(energy.dual().as_ref(), &[2.0 * 5.0_f64.exp(), 4.0 * 5.0_f64.exp()]);
+/// # }
+/// ```
+pub trait Then: Sized {
+    /// Evaluate the next stage over `self`
+    fn then<Output, F: FnOnce(Self) -> Output>(self, func: F) -> Output {
+        func(self)
+    }
+}
+
+impl<D: Dual> Then for D {}
+#[cfg(feature = "alloc")]
+impl<D: Dual> Then for std::prelude::v1::Vec<D> {}
+impl<D: Dual, const N: usize> Then for [D; N] {}
+
+/// [`Dual`] wrapper with total [`Eq`], [`Ord`] and [`Hash`] by [`Dual::total_cmp`],
+/// e.g. to key memoization caches.
+///
+/// Only values are compared and hashed, derivatives are ignored
+///```
+/// use autodj::prelude::single::*;
+/// use autodj::fluid::OrderedDual;
+/// use std::collections::HashMap;
+///
+/// let mut cache = HashMap::new();
+/// let x = 2.0.into_variable();
+/// cache.insert(OrderedDual(x), x.exp());
+/// assert!(cache.contains_key(&OrderedDual(DualF64::parameter(2.0))));
+/// assert!(OrderedDual(DualF64::parameter(f64::NAN)) == OrderedDual(DualF64::parameter(f64::NAN)));
+/// assert!(OrderedDual(DualF64::parameter(-0.0)) < OrderedDual(DualF64::parameter(0.0)));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OrderedDual<D: Dual>(pub D);
+
+impl<D: Dual> PartialEq for OrderedDual<D>
+where
+    D::Value: TotalOrder,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl<D: Dual> Eq for OrderedDual<D> where D::Value: TotalOrder {}
+
+impl<D: Dual> PartialOrd for OrderedDual<D>
+where
+    D::Value: TotalOrder,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<D: Dual> Ord for OrderedDual<D>
+where
+    D::Value: TotalOrder,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl<D: Dual> Hash for OrderedDual<D>
+where
+    D::Value: TotalOrder + Float,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // bit patterns coincide exactly when values are equal in the total order
+        self.0.value().integer_decode().hash(state);
+    }
+}
+
+/// Argument outside the domain of a function, see [`Dual::try_ln`] and alike.
+/// NaN arguments are outside every domain
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DomainError {
+    /// name of the function
+    pub function: &'static str,
+}
+
+impl DomainError {
+    /// [`Err`] unless the argument is `valid`
+    const fn check(function: &'static str, valid: bool) -> core::result::Result<(), Self> {
+        if valid {
+            Ok(())
+        } else {
+            Err(Self { function })
+        }
+    }
+}
+
+impl Display for DomainError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "argument outside the domain of {}", self.function)
+    }
+}
+
+/// `1/2` for any [`Value`] implementor
+pub(crate) fn half<V: Value>() -> V {
+    (V::one() + V::one()).recip()
+}
+
+/// Write a component of a dual number with the width and precision of a formatter,
+/// e.g. of `{:8.3}`, given a sign flag (`""` or `"+"`) and a trait specifier (`""`, `"?"` or `"e"`)
+macro_rules! write_flagged {
+    ($f:expr, $number:expr, $sign:literal, $spec:literal) => {{
+        let width = $f.width().unwrap_or(0);
+        match $f.precision() {
+            Some(precision) => write!(
+                $f,
+                concat!("{0:", $sign, "1$.2$", $spec, "}"),
+                $number, width, precision
+            ),
+            None => write!($f, concat!("{0:", $sign, "1$", $spec, "}"), $number, width),
+        }
+    }};
+}
+pub(crate) use write_flagged;
+
+/// Fluid implementation of [`Display`] to use with solid structs.
+/// The alternate form labels derivatives by their keys,
+/// with `x` prepended to positions in dense storage
 pub(crate) fn display_impl<V, G, D>(dual_number: &D, f: &mut Formatter<'_>) -> Result
 where
     V: Value + Display,
-    G: Grad<V> + Display,
+    G: Grad<V> + GradIter<V> + Display,
+    G::Key: Display,
     D: Dual<Value = V, Grad = G>,
 {
-    write!(f, "{}{:+}∆", dual_number.value(), dual_number.dual())
+    if f.alternate() {
+        let prefix = if dual_number.dual().dim().is_some() {
+            "x"
+        } else {
+            ""
+        };
+        write!(f, "f = ")?;
+        write_flagged!(f, dual_number.value(), "", "")?;
+        for (key, deriv) in dual_number.dual().derivatives() {
+            write!(f, ", ∂f/∂{prefix}{key} = ")?;
+            write_flagged!(f, deriv, "", "")?;
+        }
+        return Ok(());
+    }
+    write_flagged!(f, dual_number.value(), "", "")?;
+    write_flagged!(f, dual_number.dual(), "+", "")?;
+    write!(f, "∆")
 }
 
 /// Fluid implementation of [`LowerExp`] to use with solid structs
@@ -239,5 +746,7 @@ where
     G: Grad<V> + LowerExp,
     D: Dual<Value = V, Grad = G>,
 {
-    write!(f, "{:e}{:e}∆", dual_number.value(), dual_number.dual())
+    write_flagged!(f, dual_number.value(), "", "e")?;
+    write_flagged!(f, dual_number.dual(), "", "e")?;
+    write!(f, "∆")
 }
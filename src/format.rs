@@ -0,0 +1,195 @@
+//! Configurable output of dual numbers, e.g. for terminals and logs without `∆` glyph
+//!
+//!```
+//! use autodj::prelude::array::*;
+//! use autodj::format::DisplayOptions;
+//!
+//! let [x, y, z] = [1.0, 2.0, 2e6].into_variables();
+//! let f = x * y * z;
+//! let options = DisplayOptions::new()
+//!     .symbol(" d")
+//!     .separator("; ")
+//!     .max_components(2)
+//!     .scientific_threshold(5);
+//! assert_eq!(f.format_with(&options).to_string(), "4e6+[4e6; 2e6; ...] d");
+//! assert_eq!(format!("{:.1}", f.format_with(&options)), "4.0e6+[4.0e6; 2.0e6; ...] d");
+//! ```
+
+use crate::fluid::{write_flagged, Dual, GradIter, Value};
+use std::fmt::{Display, Formatter, LowerExp, Result};
+
+/// Options of [`Dual::format_with`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DisplayOptions {
+    /// suffix marking the dual part
+    symbol: &'static str,
+    /// separator of derivatives
+    separator: &'static str,
+    /// number of derivatives printed before the ellipsis
+    max_components: Option<usize>,
+    /// decimal exponent of magnitudes printed in scientific notation
+    scientific_threshold: Option<i32>,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DisplayOptions {
+    /// Same symbol and separator as [`Display`] of dual numbers, all derivatives, no scientific notation
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            symbol: "∆",
+            separator: ", ",
+            max_components: None,
+            scientific_threshold: None,
+        }
+    }
+
+    /// Suffix marking the dual part
+    #[must_use]
+    pub const fn symbol(mut self, symbol: &'static str) -> Self {
+        self.symbol = symbol;
+        self
+    }
+
+    /// Separator of derivatives
+    #[must_use]
+    pub const fn separator(mut self, separator: &'static str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Number of derivatives printed before the ellipsis `...`
+    #[must_use]
+    pub const fn max_components(mut self, max_components: usize) -> Self {
+        self.max_components = Some(max_components);
+        self
+    }
+
+    /// Print numbers in scientific notation if their magnitude is
+    /// at least `10^exponent` or below `10^-exponent`
+    #[must_use]
+    pub const fn scientific_threshold(mut self, exponent: i32) -> Self {
+        self.scientific_threshold = Some(exponent);
+        self
+    }
+
+    /// Whether a number is printed in scientific notation
+    fn is_scientific<V: Value>(&self, number: V) -> bool {
+        let (Some(exponent), Some(ten)) = (self.scientific_threshold, V::from(10_u8)) else {
+            return false;
+        };
+        let (magnitude, bound) = (number.abs(), ten.powi(exponent));
+        magnitude <= V::max_value()
+            && !magnitude.is_zero()
+            && (magnitude >= bound || magnitude < bound.recip())
+    }
+
+    /// Write a number with width and precision of the formatter
+    fn write_number<V: Value + Display + LowerExp>(
+        &self,
+        f: &mut Formatter<'_>,
+        number: V,
+    ) -> Result {
+        if self.is_scientific(number) {
+            write_flagged!(f, number, "", "e")
+        } else {
+            write_flagged!(f, number, "", "")
+        }
+    }
+}
+
+/// Dual number displayed with [`DisplayOptions`], see [`Dual::format_with`].
+///
+/// Dense derivatives are printed as a list `[…]`, sparse ones as `{key: …}`
+/// in order of [`GradIter::derivatives`]
+#[derive(Clone, Copy, Debug)]
+pub struct Formatted<'a, D> {
+    /// dual number to display
+    dual_number: &'a D,
+    /// options of the output
+    options: &'a DisplayOptions,
+}
+
+impl<'a, D: Dual> Formatted<'a, D> {
+    /// Display a dual number with given options
+    pub(crate) const fn new(dual_number: &'a D, options: &'a DisplayOptions) -> Self {
+        Self {
+            dual_number,
+            options,
+        }
+    }
+}
+
+impl<D> Display for Formatted<'_, D>
+where
+    D: Dual,
+    D::Value: Display + LowerExp,
+    D::Grad: GradIter<D::Value>,
+    <D::Grad as GradIter<D::Value>>::Key: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let options = self.options;
+        let grad = self.dual_number.dual();
+        let dense = grad.dim().is_some();
+        options.write_number(f, *self.dual_number.value())?;
+        write!(f, "{}", if dense { "+[" } else { "+{" })?;
+        for (index, (key, deriv)) in grad.derivatives().enumerate() {
+            if index > 0 {
+                write!(f, "{}", options.separator)?;
+            }
+            if options.max_components.is_some_and(|max| index >= max) {
+                write!(f, "...")?;
+                break;
+            }
+            if !dense {
+                write!(f, "{key}: ")?;
+            }
+            options.write_number(f, deriv)?;
+        }
+        write!(f, "{}{}", if dense { "]" } else { "}" }, options.symbol)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::solid::{array, single};
+    use std::prelude::v1::ToString;
+
+    #[test]
+    fn default_options() {
+        let [x, y] = array::IntoVariables::into_variables([2.0_f64, 3.0_f64]);
+        let f = x * y;
+        let options = DisplayOptions::default();
+        assert_eq!(f.format_with(&options).to_string(), "6+[3, 2]∆");
+    }
+
+    #[test]
+    fn scientific_threshold() {
+        let f = single::IntoVariable::into_variable(1e-4_f64) * 3.0_f64;
+        let options = DisplayOptions::new().symbol("").scientific_threshold(4);
+        assert_eq!(
+            f.format_with(&options).to_string(),
+            "0.00030000000000000003+[3]"
+        );
+        let options = options.scientific_threshold(3);
+        assert_eq!(
+            f.format_with(&options).to_string(),
+            "3.0000000000000003e-4+[3]"
+        );
+        let zero = single::IntoVariable::into_variable(0.0_f64);
+        assert_eq!(zero.format_with(&options).to_string(), "0+[1]");
+    }
+
+    #[test]
+    fn no_components() {
+        let [x, y] = array::IntoVariables::into_variables([2.0_f64, 3.0_f64]);
+        let options = DisplayOptions::new().max_components(0);
+        assert_eq!(x.add_impl(&y).format_with(&options).to_string(), "5+[...]∆");
+    }
+}
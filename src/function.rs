@@ -0,0 +1,74 @@
+//! Differentiable functions accepted across the crate
+//!
+//! Closures implement the matching trait automatically,
+//! while structs implement it directly to carry their own data,
+//! so solvers, optimizers and evaluators take any of them alike.
+//! Every trait is object-safe, so functions can also be boxed and stored together
+//!
+//!```
+//! # #[cfg(feature = "alloc")] {
+//! use autodj::prelude::vector::*;
+//! use autodj::function::{DynDualFn, VectorDualFn};
+//! use autodj::solver::newton::Newton;
+//!
+//! /// `x² = target` for every component
+//! struct Squares {
+//!     target: f64,
+//! }
+//!
+//! impl VectorDualFn<DualF64> for Squares {
+//!     fn eval(&self, x: &[DualF64]) -> Vec<DualF64> {
+//!         x.iter().map(|xi| xi.mul_impl(xi) - self.target).collect()
+//!     }
+//! }
+//!
+//! let report = Newton::new().solve(&Squares { target: 4.0 }, &[1.0, 3.0]);
+//! assert!(report.solution().iter().all(|xi| (xi - 2.0).abs() < 1e-12));
+//!
+//! let norm_squared = |x: &[DualF64]| x.iter().map(|xi| xi.mul_impl(xi)).sum::<DualF64>();
+//! let x: Vec<DualF64> = vec![1.0, 2.0].into_variables();
+//! assert_eq!(norm_squared.eval(&x).value(), &5.0);
+//! # }
+//! ```
+
+use crate::fluid::Dual;
+#[cfg(feature = "alloc")]
+use std::prelude::v1::Vec;
+
+/// Function of a single dual number, e.g. an integrand or a scalar equation
+pub trait ScalarDualFn<D: Dual> {
+    /// Evaluate at a given variable
+    fn eval(&self, variable: D) -> D;
+}
+
+impl<D: Dual, F: Fn(D) -> D> ScalarDualFn<D> for F {
+    fn eval(&self, variable: D) -> D {
+        self(variable)
+    }
+}
+
+/// Function of a slice of dual numbers with a single output, e.g. an objective
+pub trait DynDualFn<D: Dual> {
+    /// Evaluate at given variables
+    fn eval(&self, variables: &[D]) -> D;
+}
+
+impl<D: Dual, F: Fn(&[D]) -> D> DynDualFn<D> for F {
+    fn eval(&self, variables: &[D]) -> D {
+        self(variables)
+    }
+}
+
+/// Function of a slice of dual numbers with several outputs, e.g. residuals of a system
+#[cfg(feature = "alloc")]
+pub trait VectorDualFn<D: Dual> {
+    /// Evaluate all outputs at given variables
+    fn eval(&self, variables: &[D]) -> Vec<D>;
+}
+
+#[cfg(feature = "alloc")]
+impl<D: Dual, F: Fn(&[D]) -> Vec<D>> VectorDualFn<D> for F {
+    fn eval(&self, variables: &[D]) -> Vec<D> {
+        self(variables)
+    }
+}
@@ -0,0 +1,358 @@
+//! Differentiable interpolation of tabulated data
+//!
+//! Tables hold plain values while query points are dual numbers,
+//! so derivatives come from local slopes of the interpolant.
+//! Outside of the table both interpolants continue their boundary pieces,
+//! while [`Table`] makes the extrapolation policy explicit for measured data
+//!
+//!```
+//! use autodj::prelude::single::*;
+//! use autodj::interp::{CubicSpline, Linear};
+//!
+//! let knots = vec![0.0, 1.0, 2.0];
+//! let values = vec![0.0, 1.0, 4.0];
+//!
+//! let linear = Linear::new(knots.clone(), values.clone()).unwrap();
+//! assert_eq!(linear.eval(&1.5.into_variable()).decompose(), (2.5, 3.0));
+//!
+//! let spline = CubicSpline::natural(knots, values).unwrap();
+//! let (value, slope) = spline.eval(&1.0.into_variable()).decompose();
+//! assert_eq!(value, 1.0);
+//! assert_eq!(slope, 2.0);
+//! ```
+#![cfg(feature = "alloc")]
+
+use crate::{
+    fluid::{half, Dual, Value},
+    solid::single::IntoVariable,
+};
+use std::prelude::v1::{vec, Vec};
+
+pub use crate::error::TableError;
+
+/// Check that knots strictly increase and match values in number, at least two of them
+fn check_table<V: Value>(knots: &[V], values: &[V]) -> Result<(), TableError> {
+    if knots.len() < 2 {
+        return Err(TableError::TooFewKnots);
+    }
+    if knots.len() != values.len() {
+        return Err(TableError::LengthMismatch {
+            knots: knots.len(),
+            values: values.len(),
+        });
+    }
+    let increasing = knots.windows(2).all(|pair| match pair {
+        [left, right] => left < right,
+        _ => false,
+    });
+    if increasing {
+        Ok(())
+    } else {
+        Err(TableError::Unordered)
+    }
+}
+
+/// Index of the left knot of the piece containing `x`, boundary pieces extended outwards
+fn locate<V: Value>(knots: &[V], x: V) -> usize {
+    knots
+        .partition_point(|&knot| knot <= x)
+        .clamp(1, knots.len().saturating_sub(1))
+        - 1
+}
+
+/// Piecewise-linear interpolant
+#[derive(Clone, Debug, PartialEq)]
+pub struct Linear<V: Value> {
+    /// strictly increasing abscissas
+    knots: Vec<V>,
+    /// tabulated values
+    values: Vec<V>,
+}
+
+impl<V: Value> Linear<V> {
+    /// Construct from a table
+    /// # Errors
+    /// [`TableError`] unless knots strictly increase and match values in number, at least two of them
+    pub fn new(knots: Vec<V>, values: Vec<V>) -> Result<Self, TableError> {
+        check_table(&knots, &values)?;
+        Ok(Self { knots, values })
+    }
+
+    /// Strictly increasing abscissas
+    #[must_use]
+    pub fn knots(&self) -> &[V] {
+        &self.knots
+    }
+
+    /// Tabulated values
+    #[must_use]
+    pub fn values(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Evaluate at a dual point
+    #[must_use]
+    pub fn eval<D: Dual<Value = V>>(&self, x: &D) -> D {
+        x.chain(|&x| {
+            let index = locate(&self.knots, x);
+            let piece = self
+                .knots
+                .get(index..=index + 1)
+                .zip(self.values.get(index..=index + 1));
+            let Some(([left, right], [left_value, right_value])) = piece else {
+                return (V::zero(), V::zero());
+            };
+            let slope = (*right_value - *left_value) / (*right - *left);
+            (*left_value + slope * (x - *left), slope)
+        })
+    }
+}
+
+/// Cubic spline interpolant, twice continuously differentiable
+#[derive(Clone, Debug, PartialEq)]
+pub struct CubicSpline<V: Value> {
+    /// strictly increasing abscissas
+    knots: Vec<V>,
+    /// tabulated values
+    values: Vec<V>,
+    /// second derivatives at knots
+    curvatures: Vec<V>,
+}
+
+impl<V: Value> CubicSpline<V> {
+    /// Natural spline with zero second derivatives at boundary knots
+    /// # Errors
+    /// [`TableError`] unless knots strictly increase and match values in number, at least two of them
+    pub fn natural(knots: Vec<V>, values: Vec<V>) -> Result<Self, TableError> {
+        check_table(&knots, &values)?;
+        // the sweep only looks up in-range knots of a checked table
+        let curvatures = natural_curvatures(&knots, &values).ok_or(TableError::TooFewKnots)?;
+        Ok(Self {
+            knots,
+            values,
+            curvatures,
+        })
+    }
+
+    /// Evaluate at a dual point
+    #[must_use]
+    pub fn eval<D: Dual<Value = V>>(&self, x: &D) -> D {
+        x.chain(|&x| {
+            let index = locate(&self.knots, x);
+            let piece = self
+                .knots
+                .get(index..=index + 1)
+                .zip(self.values.get(index..=index + 1))
+                .zip(self.curvatures.get(index..=index + 1));
+            let Some((([left, right], [left_value, right_value]), [left_curv, right_curv])) = piece
+            else {
+                return (V::zero(), V::zero());
+            };
+            let three = V::one() + V::one() + V::one();
+            let sixth = three.recip() * half();
+            let width = *right - *left;
+            let (a, b) = ((*right - x) / width, (x - *left) / width);
+            let value = a * *left_value
+                + b * *right_value
+                + ((a * a * a - a) * *left_curv + (b * b * b - b) * *right_curv)
+                    * width
+                    * width
+                    * sixth;
+            let slope = (*right_value - *left_value) / width
+                + ((three * b * b - V::one()) * *right_curv
+                    - (three * a * a - V::one()) * *left_curv)
+                    * width
+                    * sixth;
+            (value, slope)
+        })
+    }
+}
+
+/// Second derivatives of the natural spline by the tridiagonal (Thomas) algorithm
+fn natural_curvatures<V: Value>(knots: &[V], values: &[V]) -> Option<Vec<V>> {
+    let size = knots.len();
+    let two = V::one() + V::one();
+    let six = two + two + two;
+    // forward sweep: modified diagonal and right-hand side of interior equations
+    let mut diagonal = vec![V::zero(); size];
+    let mut rhs = vec![V::zero(); size];
+    for index in 1..size - 1 {
+        let [left, middle, right] = knots.get(index - 1..=index + 1)? else {
+            return None;
+        };
+        let [left_value, middle_value, right_value] = values.get(index - 1..=index + 1)? else {
+            return None;
+        };
+        let (before, after) = (*middle - *left, *right - *middle);
+        let mut pivot = two * (before + after);
+        let mut load =
+            six * ((*right_value - *middle_value) / after - (*middle_value - *left_value) / before);
+        if index > 1 {
+            let factor = before / *diagonal.get(index - 1)?;
+            pivot -= factor * before;
+            load -= factor * *rhs.get(index - 1)?;
+        }
+        *diagonal.get_mut(index)? = pivot;
+        *rhs.get_mut(index)? = load;
+    }
+    // backward substitution with zero boundary curvatures
+    let mut curvatures = vec![V::zero(); size];
+    for index in (1..size - 1).rev() {
+        let after = *knots.get(index + 1)? - *knots.get(index)?;
+        let next = *curvatures.get(index + 1)?;
+        *curvatures.get_mut(index)? = (*rhs.get(index)? - after * next) / *diagonal.get(index)?;
+    }
+    Some(curvatures)
+}
+
+/// Behaviour of [`Table`] beyond its first and last knots
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Extrapolation {
+    /// hold boundary values, with zero sensitivity
+    #[default]
+    Clamp,
+    /// continue boundary segments
+    Linear,
+}
+
+/// Piecewise-linear lookup table of measured data with an [`Extrapolation`] policy
+///
+///```
+/// use autodj::prelude::single::*;
+/// use autodj::interp::{Extrapolation, Table};
+///
+/// let table = Table::new(vec![0.0, 1.0], vec![1.0, 3.0]).unwrap();
+/// assert_eq!(table.lookup(&2.0.into_variable()).decompose(), (3.0, 0.0));
+///
+/// let table = table.extrapolation(Extrapolation::Linear);
+/// assert_eq!(table.lookup(&2.0.into_variable()).decompose(), (5.0, 2.0));
+/// assert_eq!(table.sensitivity(0.5), 2.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Table<V: Value> {
+    /// interpolant within the table
+    interpolant: Linear<V>,
+    /// policy beyond the table
+    extrapolation: Extrapolation,
+}
+
+impl<V: Value> Table<V> {
+    /// Construct from a table with [`Extrapolation::Clamp`]
+    /// # Errors
+    /// [`TableError`] unless knots strictly increase and match values in number, at least two of them
+    pub fn new(knots: Vec<V>, values: Vec<V>) -> Result<Self, TableError> {
+        Linear::new(knots, values).map(|interpolant| Self {
+            interpolant,
+            extrapolation: Extrapolation::default(),
+        })
+    }
+
+    /// Set the policy beyond the table
+    #[must_use]
+    pub fn extrapolation(mut self, extrapolation: Extrapolation) -> Self {
+        self.extrapolation = extrapolation;
+        self
+    }
+
+    /// Look up a dual point
+    #[must_use]
+    pub fn lookup<D: Dual<Value = V>>(&self, x: &D) -> D {
+        match (self.extrapolation, self.boundary(*x.value())) {
+            (Extrapolation::Clamp, Some(value)) => D::parameter(value),
+            _ => self.interpolant.eval(x),
+        }
+    }
+
+    /// Sensitivity `∂output/∂x` of the lookup at a point
+    #[must_use]
+    pub fn sensitivity(&self, x: V) -> V {
+        *self.lookup(&x.into_variable()).dual()
+    }
+
+    /// Boundary value if a point lies beyond the table
+    fn boundary(&self, x: V) -> Option<V> {
+        let knots = self.interpolant.knots();
+        let values = self.interpolant.values();
+        match (
+            knots.first().zip(values.first()),
+            knots.last().zip(values.last()),
+        ) {
+            (Some((&first, &value)), _) if x < first => Some(value),
+            (_, Some((&last, &value))) if x > last => Some(value),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+    use crate::solid::single::DualNumber;
+
+    #[test]
+    fn invalid_tables() {
+        assert_eq!(
+            Linear::new(vec![0.0_f64], vec![1.0_f64]),
+            Err(TableError::TooFewKnots)
+        );
+        assert_eq!(
+            Linear::new(vec![0.0_f64, 0.0_f64], vec![1.0_f64, 2.0_f64]),
+            Err(TableError::Unordered)
+        );
+        assert_eq!(
+            CubicSpline::natural(vec![0.0_f64, 1.0_f64], vec![1.0_f64]),
+            Err(TableError::LengthMismatch {
+                knots: 2,
+                values: 1
+            })
+        );
+    }
+
+    #[test]
+    fn linear_extrapolation() {
+        let table = Linear::new(vec![0.0_f64, 1.0_f64], vec![1.0_f64, 3.0_f64]);
+        let x: DualNumber<f64> = (-1.0_f64).into_variable();
+        assert_eq!(
+            table.map(|table| table.eval(&x).decompose()),
+            Ok((-1.0_f64, 2.0_f64))
+        );
+    }
+
+    #[test]
+    fn clamped_table_inside() {
+        let table = Table::new(vec![0.0_f64, 1.0_f64], vec![1.0_f64, 3.0_f64]);
+        let x: DualNumber<f64> = 0.25_f64.into_variable();
+        assert_eq!(
+            table.map(|table| table.lookup(&x).decompose()),
+            Ok((1.5_f64, 2.0_f64))
+        );
+    }
+
+    #[test]
+    fn spline_reproduces_lines_and_knots() {
+        let knots = vec![0.0_f64, 0.5_f64, 2.0_f64, 3.0_f64];
+        let line: Vec<f64> = knots.iter().map(|&x| 2.0_f64 * x - 1.0_f64).collect();
+        let spline = CubicSpline::natural(knots, line);
+        let x: DualNumber<f64> = 1.25_f64.into_variable();
+        let (value, slope) =
+            spline.map_or((0.0_f64, 0.0_f64), |spline| spline.eval(&x).decompose());
+        assert!((value - 1.5_f64).abs() < 1e-12_f64);
+        assert!((slope - 2.0_f64).abs() < 1e-12_f64);
+    }
+
+    #[test]
+    fn spline_slope_is_continuous() {
+        let spline = CubicSpline::natural(
+            vec![0.0_f64, 1.0_f64, 3.0_f64, 4.0_f64],
+            vec![0.0_f64, 2.0_f64, 1.0_f64, 3.0_f64],
+        );
+        let slope_at = |x: f64| {
+            spline
+                .as_ref()
+                .map_or(0.0_f64, |spline| *spline.eval(&x.into_variable()).dual())
+        };
+        assert!((slope_at(1.0_f64 - 1e-9_f64) - slope_at(1.0_f64 + 1e-9_f64)).abs() < 1e-6_f64);
+    }
+}
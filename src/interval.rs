@@ -0,0 +1,712 @@
+//! Interval values for enclosures of both functions and their derivatives
+//!
+//! [`Interval`] implements [`Real`], so it serves as [`Value`](crate::fluid::Value) of any dual number.
+//! Every operation rounds its bounds outwards, so the value and every derivative
+//! of a dual number over intervals enclose the exact ones over all points of the input intervals.
+//! Arithmetic and [`Real::sqrt`] are correctly rounded by IEEE 754, so their enclosures are rigorous.
+//! Elementary functions additionally rely on the platform math library being accurate to an ulp.
+//!
+//! An interval Newton step `N(X) = m − f(m) / f′(X)` then verifies and refines a root
+//!
+//!```
+//! use autodj::prelude::single::*;
+//! use autodj::interval::Interval;
+//!
+//! let f = |x: DualNumber<Interval>| x * x - Interval::point(2.0);
+//! let mut enclosure = Interval::new(1.0, 2.0).expect("ordered bounds");
+//! for _ in 0..5 {
+//!     let slope = *f(enclosure.into_variable()).dual();
+//!     let mid = Interval::point(enclosure.mid());
+//!     let newton = mid - *f(mid.into_variable()).value() / slope;
+//!     enclosure = newton.intersect(enclosure).expect("a root in the enclosure");
+//! }
+//! assert!(enclosure.contains(2.0_f64.sqrt()));
+//! assert!(enclosure.width() < 1e-12);
+//! ```
+#![cfg(feature = "interval")]
+
+use core::{
+    cmp::Ordering,
+    f64::consts::{FRAC_PI_2, PI, TAU},
+    fmt::{Display, Formatter},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign},
+};
+use num_traits::{real::Real, Num, NumCast, One, ToPrimitive, Zero};
+
+/// Closed interval of [`f64`] bounds.
+///
+/// Intervals are ordered only when they are disjoint or identical
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Interval {
+    /// lower bound
+    inf: f64,
+    /// upper bound
+    sup: f64,
+}
+
+impl Interval {
+    /// Whole real line
+    pub const ENTIRE: Self = Self {
+        inf: f64::NEG_INFINITY,
+        sup: f64::INFINITY,
+    };
+
+    /// Result of functions evaluated entirely outside of their domains
+    pub const NAN: Self = Self {
+        inf: f64::NAN,
+        sup: f64::NAN,
+    };
+
+    /// Interval of given bounds. [`None`] unless `inf ≤ sup`
+    #[must_use]
+    pub fn new(inf: f64, sup: f64) -> Option<Self> {
+        (inf <= sup).then_some(Self { inf, sup })
+    }
+
+    /// Degenerate interval of a single point
+    #[must_use]
+    pub const fn point(value: f64) -> Self {
+        Self {
+            inf: value,
+            sup: value,
+        }
+    }
+
+    /// Lower bound
+    #[must_use]
+    pub fn inf(&self) -> f64 {
+        self.inf
+    }
+
+    /// Upper bound
+    #[must_use]
+    pub fn sup(&self) -> f64 {
+        self.sup
+    }
+
+    /// Midpoint, e.g. to pick a point for a Newton step
+    #[must_use]
+    pub fn mid(&self) -> f64 {
+        self.inf.midpoint(self.sup)
+    }
+
+    /// Distance between bounds, rounded upwards
+    #[must_use]
+    pub fn width(&self) -> f64 {
+        upper(self.sup - self.inf)
+    }
+
+    /// Whether a point lies within bounds
+    #[must_use]
+    pub fn contains(&self, value: f64) -> bool {
+        self.inf <= value && value <= self.sup
+    }
+
+    /// Whether `self` lies within `other`
+    #[must_use]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        other.inf <= self.inf && self.sup <= other.sup
+    }
+
+    /// Common part of two intervals. [`None`] if they are disjoint
+    #[must_use]
+    pub fn intersect(self, other: Self) -> Option<Self> {
+        Self::new(self.inf.max(other.inf), self.sup.min(other.sup))
+    }
+
+    /// Smallest interval containing both
+    #[must_use]
+    pub fn hull(self, other: Self) -> Self {
+        Self {
+            inf: self.inf.min(other.inf),
+            sup: self.sup.max(other.sup),
+        }
+    }
+
+    /// Enclosure of a non-decreasing library function
+    fn increasing(self, func: fn(f64) -> f64) -> Self {
+        Self {
+            inf: lower_lib(func(self.inf)),
+            sup: upper_lib(func(self.sup)),
+        }
+    }
+
+    /// Enclosure of a non-increasing library function
+    fn decreasing(self, func: fn(f64) -> f64) -> Self {
+        Self {
+            inf: lower_lib(func(self.sup)),
+            sup: upper_lib(func(self.inf)),
+        }
+    }
+
+    /// Part of `self` within a domain. [`Interval::NAN`] outside of it
+    fn clip(self, min: f64, max: f64) -> Self {
+        Self::new(self.inf.max(min), self.sup.min(max)).unwrap_or(Self::NAN)
+    }
+
+    /// Whether `phase + k·period` may lie within bounds for some integer `k`.
+    /// Errs towards `true`, which only widens enclosures
+    fn may_contain_phase(&self, phase: f64, period: f64) -> bool {
+        let turns = (self.inf - phase) / period;
+        let tolerance = 4.0_f64 * f64::EPSILON * turns.abs().max(1.0);
+        let candidate = phase + (turns - tolerance).ceil() * period;
+        candidate - self.sup <= 8.0_f64 * f64::EPSILON * candidate.abs().max(1.0)
+    }
+
+    /// Enclosure of a `2π`-periodic function within `[-1, 1]`
+    /// with maxima at `peak` and minima at `peak + π`
+    fn periodic(self, func: fn(f64) -> f64, peak: f64) -> Self {
+        let unit = Self {
+            inf: -1.0,
+            sup: 1.0,
+        };
+        if self.width().partial_cmp(&TAU) != Some(Ordering::Less) {
+            return unit;
+        }
+        let (left, right) = (func(self.inf), func(self.sup));
+        Self {
+            inf: if self.may_contain_phase(peak + PI, TAU) {
+                -1.0
+            } else {
+                lower_lib(left.min(right)).max(-1.0)
+            },
+            sup: if self.may_contain_phase(peak, TAU) {
+                1.0
+            } else {
+                upper_lib(left.max(right)).min(1.0)
+            },
+        }
+    }
+}
+
+/// Lower bound of a correctly rounded result
+fn lower(value: f64) -> f64 {
+    value.next_down()
+}
+
+/// Upper bound of a correctly rounded result
+fn upper(value: f64) -> f64 {
+    value.next_up()
+}
+
+/// Lower bound of a math library result accurate to an ulp
+fn lower_lib(value: f64) -> f64 {
+    value.next_down().next_down()
+}
+
+/// Upper bound of a math library result accurate to an ulp
+fn upper_lib(value: f64) -> f64 {
+    value.next_up().next_up()
+}
+
+/// Product of bounds where zero annihilates infinities
+fn mul_bounds(lhs: f64, rhs: f64) -> f64 {
+    if lhs.is_zero() || rhs.is_zero() {
+        0.0
+    } else {
+        lhs * rhs
+    }
+}
+
+impl From<f64> for Interval {
+    fn from(value: f64) -> Self {
+        Self::point(value)
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "[{}, {}]", self.inf, self.sup)
+    }
+}
+
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            Some(Ordering::Equal)
+        } else if self.sup < other.inf {
+            Some(Ordering::Less)
+        } else if self.inf > other.sup {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
+    }
+}
+
+impl Neg for Interval {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            inf: -self.sup,
+            sup: -self.inf,
+        }
+    }
+}
+
+impl Add for Interval {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            inf: lower(self.inf + rhs.inf),
+            sup: upper(self.sup + rhs.sup),
+        }
+    }
+}
+
+impl Sub for Interval {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            inf: lower(self.inf - rhs.sup),
+            sup: upper(self.sup - rhs.inf),
+        }
+    }
+}
+
+impl Mul for Interval {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let products = [
+            mul_bounds(self.inf, rhs.inf),
+            mul_bounds(self.inf, rhs.sup),
+            mul_bounds(self.sup, rhs.inf),
+            mul_bounds(self.sup, rhs.sup),
+        ];
+        Self {
+            inf: lower(products.into_iter().fold(f64::INFINITY, f64::min)),
+            sup: upper(products.into_iter().fold(f64::NEG_INFINITY, f64::max)),
+        }
+    }
+}
+
+impl Div for Interval {
+    type Output = Self;
+
+    #[allow(
+        clippy::suspicious_arithmetic_impl,
+        reason = "division multiplies by the enclosure of the reciprocal"
+    )]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.recip()
+    }
+}
+
+impl Rem for Interval {
+    type Output = Self;
+
+    /// Crude enclosure bounded by the magnitude of the divisor
+    fn rem(self, rhs: Self) -> Self {
+        let bound = rhs.abs().sup;
+        Self {
+            inf: if self.inf >= 0.0 { 0.0 } else { -bound },
+            sup: if self.sup <= 0.0 { 0.0 } else { bound },
+        }
+    }
+}
+
+impl AddAssign for Interval {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Interval {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Interval {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for Interval {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl RemAssign for Interval {
+    fn rem_assign(&mut self, rhs: Self) {
+        *self = *self % rhs;
+    }
+}
+
+impl Zero for Interval {
+    fn zero() -> Self {
+        Self::point(0.0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.inf.is_zero() && self.sup.is_zero()
+    }
+}
+
+impl One for Interval {
+    fn one() -> Self {
+        Self::point(1.0)
+    }
+}
+
+impl Num for Interval {
+    type FromStrRadixErr = <f64 as Num>::FromStrRadixErr;
+
+    /// Point interval of the parsed [`f64`]
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        f64::from_str_radix(str, radix).map(Self::point)
+    }
+}
+
+/// Conversions take the midpoint
+impl ToPrimitive for Interval {
+    fn to_i64(&self) -> Option<i64> {
+        self.mid().to_i64()
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.mid().to_u64()
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        Some(self.mid())
+    }
+}
+
+/// Conversions give point intervals of the nearest [`f64`]
+impl NumCast for Interval {
+    fn from<T: ToPrimitive>(n: T) -> Option<Self> {
+        n.to_f64().map(Self::point)
+    }
+}
+
+impl Real for Interval {
+    fn min_value() -> Self {
+        Self::point(f64::MIN)
+    }
+
+    fn min_positive_value() -> Self {
+        Self::point(f64::MIN_POSITIVE)
+    }
+
+    fn epsilon() -> Self {
+        Self::point(f64::EPSILON)
+    }
+
+    fn max_value() -> Self {
+        Self::point(f64::MAX)
+    }
+
+    fn floor(self) -> Self {
+        Self {
+            inf: self.inf.floor(),
+            sup: self.sup.floor(),
+        }
+    }
+
+    fn ceil(self) -> Self {
+        Self {
+            inf: self.inf.ceil(),
+            sup: self.sup.ceil(),
+        }
+    }
+
+    fn round(self) -> Self {
+        Self {
+            inf: self.inf.round(),
+            sup: self.sup.round(),
+        }
+    }
+
+    fn trunc(self) -> Self {
+        Self {
+            inf: self.inf.trunc(),
+            sup: self.sup.trunc(),
+        }
+    }
+
+    fn fract(self) -> Self {
+        self - self.trunc()
+    }
+
+    fn abs(self) -> Self {
+        if self.inf >= 0.0 {
+            self
+        } else if self.sup <= 0.0 {
+            -self
+        } else {
+            Self {
+                inf: 0.0,
+                sup: self.sup.max(-self.inf),
+            }
+        }
+    }
+
+    fn signum(self) -> Self {
+        Self {
+            inf: self.inf.signum(),
+            sup: self.sup.signum(),
+        }
+    }
+
+    fn is_sign_positive(self) -> bool {
+        self.inf.is_sign_positive()
+    }
+
+    fn is_sign_negative(self) -> bool {
+        self.sup.is_sign_negative()
+    }
+
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        self * a + b
+    }
+
+    fn recip(self) -> Self {
+        if self.contains(0.0) {
+            Self::ENTIRE
+        } else {
+            Self {
+                inf: lower(self.sup.recip()),
+                sup: upper(self.inf.recip()),
+            }
+        }
+    }
+
+    fn powi(self, n: i32) -> Self {
+        let mut base = if n % 2_i32 == 0_i32 { self.abs() } else { self };
+        let mut power = Self::one();
+        let mut exponent = n.unsigned_abs();
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                power *= base;
+            }
+            base *= base;
+            exponent /= 2;
+        }
+        if n < 0 {
+            power.recip()
+        } else {
+            power
+        }
+    }
+
+    fn powf(self, n: Self) -> Self {
+        match n.inf.to_i32() {
+            Some(integer) if n.is_subset(&Self::point(integer.into())) => self.powi(integer),
+            _ => (n * self.ln()).exp(),
+        }
+    }
+
+    fn sqrt(self) -> Self {
+        let domain = self.clip(0.0, f64::INFINITY);
+        Self {
+            inf: lower(domain.inf.sqrt()).max(0.0),
+            sup: upper(domain.sup.sqrt()),
+        }
+    }
+
+    fn exp(self) -> Self {
+        self.increasing(f64::exp)
+    }
+
+    fn exp2(self) -> Self {
+        self.increasing(f64::exp2)
+    }
+
+    fn ln(self) -> Self {
+        self.clip(0.0, f64::INFINITY).increasing(f64::ln)
+    }
+
+    fn log(self, base: Self) -> Self {
+        self.ln() / base.ln()
+    }
+
+    fn log2(self) -> Self {
+        self.clip(0.0, f64::INFINITY).increasing(f64::log2)
+    }
+
+    fn log10(self) -> Self {
+        self.clip(0.0, f64::INFINITY).increasing(f64::log10)
+    }
+
+    fn to_degrees(self) -> Self {
+        self.increasing(f64::to_degrees)
+    }
+
+    fn to_radians(self) -> Self {
+        self.increasing(f64::to_radians)
+    }
+
+    fn max(self, other: Self) -> Self {
+        Self {
+            inf: self.inf.max(other.inf),
+            sup: self.sup.max(other.sup),
+        }
+    }
+
+    fn min(self, other: Self) -> Self {
+        Self {
+            inf: self.inf.min(other.inf),
+            sup: self.sup.min(other.sup),
+        }
+    }
+
+    fn abs_sub(self, other: Self) -> Self {
+        (self - other).max(Self::zero())
+    }
+
+    fn cbrt(self) -> Self {
+        self.increasing(f64::cbrt)
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        (self.powi(2) + other.powi(2)).sqrt()
+    }
+
+    fn sin(self) -> Self {
+        self.periodic(f64::sin, FRAC_PI_2)
+    }
+
+    fn cos(self) -> Self {
+        self.periodic(f64::cos, 0.0)
+    }
+
+    fn tan(self) -> Self {
+        if self.width().partial_cmp(&PI) != Some(Ordering::Less)
+            || self.may_contain_phase(FRAC_PI_2, PI)
+        {
+            Self::ENTIRE
+        } else {
+            self.increasing(f64::tan)
+        }
+    }
+
+    fn asin(self) -> Self {
+        self.clip(-1.0, 1.0).increasing(f64::asin)
+    }
+
+    fn acos(self) -> Self {
+        self.clip(-1.0, 1.0).decreasing(f64::acos)
+    }
+
+    fn atan(self) -> Self {
+        self.increasing(f64::atan)
+    }
+
+    /// Enclosure of `atan(self / other)` for positive `other`, `[-π, π]` otherwise
+    fn atan2(self, other: Self) -> Self {
+        if other.inf > 0.0 {
+            (self / other).atan()
+        } else {
+            Self {
+                inf: lower(-PI),
+                sup: upper(PI),
+            }
+        }
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        (self.sin(), self.cos())
+    }
+
+    fn exp_m1(self) -> Self {
+        self.increasing(f64::exp_m1)
+    }
+
+    fn ln_1p(self) -> Self {
+        self.clip(-1.0, f64::INFINITY).increasing(f64::ln_1p)
+    }
+
+    fn sinh(self) -> Self {
+        self.increasing(f64::sinh)
+    }
+
+    fn cosh(self) -> Self {
+        self.abs().increasing(f64::cosh)
+    }
+
+    fn tanh(self) -> Self {
+        self.increasing(f64::tanh)
+    }
+
+    fn asinh(self) -> Self {
+        self.increasing(f64::asinh)
+    }
+
+    fn acosh(self) -> Self {
+        self.clip(1.0, f64::INFINITY).increasing(f64::acosh)
+    }
+
+    fn atanh(self) -> Self {
+        self.clip(-1.0, 1.0).increasing(f64::atanh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fluid::Dual, solid::single::IntoVariable};
+
+    /// Interval of given bounds for tests
+    fn interval(inf: f64, sup: f64) -> Interval {
+        Interval::new(inf, sup).unwrap_or(Interval::NAN)
+    }
+
+    #[test]
+    fn encloses_points() {
+        let x = interval(-0.5_f64, 2.5_f64);
+        let f = |x: Interval| (x.sin() * x.exp() - x.powi(2)).abs().sqrt();
+        let enclosure = f(x);
+        for step in 0..=30_u8 {
+            let point = -0.5_f64 + <f64 as From<u8>>::from(step) / 10.0_f64;
+            let exact = (point.sin() * point.exp() - point.powi(2)).abs().sqrt();
+            assert!(enclosure.contains(exact), "{exact} ∉ {enclosure}");
+        }
+    }
+
+    #[test]
+    fn encloses_derivatives() {
+        let x = interval(0.5_f64, 1.5_f64);
+        let f = x.into_variable().cos().powf(Interval::point(3.0_f64));
+        for step in 0..=10_u8 {
+            let point = 0.5_f64 + <f64 as From<u8>>::from(step) / 10.0_f64;
+            let exact = -3.0_f64 * point.cos().powi(2) * point.sin();
+            assert!(f.dual().contains(exact), "{exact} ∉ {}", f.dual());
+        }
+    }
+
+    #[test]
+    fn periodic_extrema() {
+        let around_peak = interval(1.0_f64, 2.0_f64).sin();
+        assert!(around_peak.contains(1.0_f64));
+        assert!(around_peak.inf() > 0.8_f64);
+        assert!(interval(3.0_f64, 3.5_f64).cos().contains(-1.0_f64));
+        assert_eq!(
+            interval(0.0_f64, 7.0_f64).sin(),
+            interval(-1.0_f64, 1.0_f64)
+        );
+        assert_eq!(interval(1.0_f64, 2.0_f64).tan(), Interval::ENTIRE);
+    }
+
+    #[test]
+    fn ordering_of_disjoint() {
+        assert!(interval(0.0_f64, 1.0_f64) < interval(2.0_f64, 3.0_f64));
+        assert!(interval(0.0_f64, 2.0_f64)
+            .partial_cmp(&interval(1.0_f64, 3.0_f64))
+            .is_none());
+    }
+
+    #[test]
+    fn reciprocals() {
+        let recip = interval(1.0_f64, 2.0_f64).recip();
+        assert!(interval(0.5_f64, 1.0_f64).is_subset(&recip));
+        assert!(recip.width() < 0.5_f64 + 4.0_f64 * f64::EPSILON);
+        assert_eq!(interval(-1.0_f64, 1.0_f64).recip(), Interval::ENTIRE);
+    }
+}
@@ -1,53 +1,140 @@
-#![doc = include_str!("../README.md")]
+#![cfg_attr(feature = "alloc", doc = include_str!("../README.md"))]
+#![cfg_attr(
+    not(feature = "alloc"),
+    doc = "Automatic differentiation library. Examples of the README need feature `alloc`"
+)]
 #![no_std]
 
 extern crate no_std_compat as std;
 
+pub mod activations;
+pub mod assembly;
+pub mod batch;
+pub mod blend;
+pub mod calibration;
+mod check;
+pub mod chunked;
+pub mod cstep;
+pub mod error;
+pub mod eval;
 pub mod fluid;
+pub mod format;
+pub mod function;
+pub mod interp;
+pub mod interval;
+mod linalg;
+pub mod matrix;
+pub mod optim;
+pub mod parallel;
+pub mod quad;
+pub mod reduce;
+pub mod report;
+pub mod scaling;
+pub mod smooth;
 pub mod solid;
+pub mod solver;
+pub mod sparsity;
+pub mod special;
+pub mod stats;
+pub mod testfns;
+pub mod units;
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "alloc")]
+pub use eval::{jacobian, value_and_grad};
+
+#[cfg(feature = "derive")]
+pub use autodj_derive::{dualize, DualStruct};
+
 // NOTE: do not use inside the library itself
 /// Set of preludes
 pub mod prelude {
     /// Prelude for working with the trait [`crate::fluid::Dual`]
     pub mod fluid {
-        pub use crate::fluid::{Dual, Grad, Value};
+        pub use crate::fluid::{Dual, Grad, GradIter, Then, Value};
     }
 
     /// Prelude for working with generic [`crate::solid::DualNumber`]
     pub mod solid {
-        pub use crate::fluid::{Dual, Grad, Value};
+        pub use crate::fluid::{Dual, Grad, GradIter, Then, Value};
         pub use crate::solid::DualNumber;
     }
 
     /// Prelude for working with [`crate::solid::single::DualNumber`]
     pub mod single {
-        pub use crate::fluid::{Dual, Grad, Value};
+        pub use crate::fluid::{Dual, Grad, GradIter, Then, Value};
 
         pub use crate::solid::single::*;
     }
 
     /// Prelude for working with [`crate::solid::array::DualNumber`]
     pub mod array {
-        pub use crate::fluid::{Dual, Grad, Value};
+        pub use crate::fluid::{Dual, Grad, GradIter, Then, Value};
 
         pub use crate::solid::array::*;
     }
 
+    /// Prelude for working with [`crate::solid::fixed::DualNumber`]
+    pub mod fixed {
+        pub use crate::fluid::{Dual, Grad, GradIter, Then, Value};
+
+        pub use crate::solid::fixed::*;
+    }
+
+    /// Prelude for working with [`crate::solid::heapless::DualNumber`]
+    #[cfg(feature = "heapless")]
+    pub mod heapless {
+        pub use crate::fluid::{Dual, Grad, GradIter, Then, Value};
+
+        pub use crate::solid::heapless::*;
+    }
+
+    /// Prelude for working with [`crate::solid::simd::DualNumber`]
+    #[cfg(feature = "simd")]
+    pub mod simd {
+        pub use crate::fluid::{Dual, Grad, GradIter, Then, Value};
+
+        pub use crate::solid::simd::*;
+    }
+
+    /// Prelude for working with [`crate::solid::smallvec::DualNumber`]
+    #[cfg(feature = "smallvec")]
+    pub mod smallvec {
+        pub use crate::fluid::{Dual, Grad, GradIter, Then, Value};
+
+        pub use crate::solid::smallvec::*;
+    }
+
     /// Prelude for working with [`crate::solid::vector::DualNumber`]
+    #[cfg(feature = "alloc")]
     pub mod vector {
-        pub use crate::fluid::{Dual, Grad, Value};
+        pub use crate::fluid::{Dual, Grad, GradIter, Then, Value};
 
         pub use crate::solid::vector::*;
     }
 
+    /// Prelude for working with [`crate::solid::sparse::indexed::DualNumber`]
+    #[cfg(feature = "sparse")]
+    pub mod indexed {
+        pub use crate::fluid::{Dual, Grad, GradIter, Then, Value};
+
+        pub use crate::solid::sparse::{self, indexed::*};
+    }
+
+    /// Prelude for working with [`crate::solid::sparse::named::DualNumber`]
+    #[cfg(feature = "sparse")]
+    pub mod named {
+        pub use crate::fluid::{Dual, Grad, GradIter, Then, Value};
+
+        pub use crate::solid::sparse::{self, named::*};
+    }
+
     /// Prelude for working with [`crate::solid::sparse::uuid::DualNumber`]
     #[cfg(feature = "uuid")]
     pub mod uuid {
-        pub use crate::fluid::{Dual, Grad, Value};
+        pub use crate::fluid::{Dual, Grad, GradIter, Then, Value};
 
         pub use crate::solid::sparse::{self, uuid::*};
     }
@@ -0,0 +1,176 @@
+//! Minimal dense linear algebra for built-in solvers
+#![cfg(feature = "alloc")]
+#![allow(
+    clippy::redundant_pub_crate,
+    reason = "conflicts with `unreachable_pub` for private modules"
+)]
+
+use crate::fluid::Value;
+use std::prelude::v1::{vec, Vec};
+
+/// Solve a square system `matrix * x = rhs` by Gaussian elimination with partial pivoting.
+///
+/// `matrix` is given by rows. Returns [`None`] for singular or malformed systems
+pub(crate) fn solve<V: Value>(matrix: Vec<Vec<V>>, rhs: Vec<V>) -> Option<Vec<V>> {
+    let columns = rhs.into_iter().map(|value| vec![value]).collect();
+    solve_many(matrix, columns)?
+        .into_iter()
+        .map(|row| row.first().copied())
+        .collect()
+}
+
+/// Solve `matrix * X = rhs` for several right-hand sides at once,
+/// sharing a single elimination of `matrix`.
+///
+/// `matrix`, `rhs` and the solution are given by rows.
+/// Returns [`None`] for singular or malformed systems
+pub(crate) fn solve_many<V: Value>(
+    mut matrix: Vec<Vec<V>>,
+    mut rhs: Vec<Vec<V>>,
+) -> Option<Vec<Vec<V>>> {
+    let size = rhs.len();
+    let width = rhs.first().map_or(0, Vec::len);
+    if matrix.len() != size
+        || matrix.iter().any(|row| row.len() != size)
+        || rhs.iter().any(|row| row.len() != width)
+    {
+        return None;
+    }
+    for column in 0..size {
+        let pivot = (column..size).try_fold(column, |best, candidate| {
+            let best_value = matrix.get(best)?.get(column)?.abs();
+            let candidate_value = matrix.get(candidate)?.get(column)?.abs();
+            Some(if candidate_value > best_value {
+                candidate
+            } else {
+                best
+            })
+        })?;
+        matrix.swap(column, pivot);
+        rhs.swap(column, pivot);
+
+        let (upper, lower) = matrix.split_at_mut(column + 1);
+        let (rhs_upper, rhs_lower) = rhs.split_at_mut(column + 1);
+        let pivot_row = upper.last()?;
+        let pivot_rhs = rhs_upper.last()?;
+        let pivot_value = *pivot_row.get(column)?;
+        let pivot_magnitude = pivot_value.abs();
+        // also rejects NaN and infinite pivots
+        if !(V::zero() < pivot_magnitude && pivot_magnitude <= V::max_value()) {
+            return None;
+        }
+        for (row, row_rhs) in lower.iter_mut().zip(rhs_lower.iter_mut()) {
+            let factor = *row.get(column)? / pivot_value;
+            for (elem, &pivot_elem) in row.iter_mut().zip(pivot_row).skip(column) {
+                *elem -= factor * pivot_elem;
+            }
+            for (elem, &pivot_elem) in row_rhs.iter_mut().zip(pivot_rhs) {
+                *elem -= factor * pivot_elem;
+            }
+        }
+    }
+
+    let mut solution = vec![vec![V::zero(); width]; size];
+    for index in (0..size).rev() {
+        let row = matrix.get(index)?;
+        let diagonal = *row.get(index)?;
+        let (upper, lower) = solution.split_at_mut(index + 1);
+        let unknown = upper.last_mut()?;
+        unknown.clone_from(rhs.get(index)?);
+        for (&coefficient, known) in row.iter().skip(index + 1).zip(lower.iter()) {
+            for (elem, &value) in unknown.iter_mut().zip(known) {
+                *elem -= coefficient * value;
+            }
+        }
+        for elem in unknown {
+            *elem = *elem / diagonal;
+        }
+    }
+    Some(solution)
+}
+
+/// Normal equations `JᵀJ` and `Jᵀ rhs` of a Jacobian given by rows of length `columns`
+pub(crate) fn normal_equations<V: Value>(
+    jacobian: &[Vec<V>],
+    rhs: &[V],
+    columns: usize,
+) -> (Vec<Vec<V>>, Vec<V>) {
+    let mut normal = vec![vec![V::zero(); columns]; columns];
+    let mut projected = vec![V::zero(); columns];
+    for (row, &row_rhs) in jacobian.iter().zip(rhs) {
+        for ((normal_row, projected_elem), &left) in
+            normal.iter_mut().zip(projected.iter_mut()).zip(row)
+        {
+            *projected_elem += left * row_rhs;
+            for (normal_elem, &right) in normal_row.iter_mut().zip(row) {
+                *normal_elem += left * right;
+            }
+        }
+    }
+    (normal, projected)
+}
+
+/// Solve a linear least-squares problem `jacobian * x ≈ rhs`
+/// via damped normal equations `(JᵀJ + damping·I) x = Jᵀ rhs`.
+///
+/// `jacobian` is given by rows of length `columns`
+pub(crate) fn least_squares<V: Value>(
+    jacobian: &[Vec<V>],
+    rhs: &[V],
+    columns: usize,
+    damping: V,
+) -> Option<Vec<V>> {
+    let (mut normal, projected) = normal_equations(jacobian, rhs, columns);
+    for (index, normal_row) in normal.iter_mut().enumerate() {
+        *normal_row.get_mut(index)? += damping;
+    }
+    solve(normal, projected)
+}
+
+/// Dot product of two slices of values
+pub(crate) fn dot<V: Value>(left: &[V], right: &[V]) -> V {
+    left.iter()
+        .zip(right)
+        .fold(V::zero(), |acc, (&left, &right)| acc + left * right)
+}
+
+/// Product of a matrix given by rows and a vector
+pub(crate) fn mul_vec<V: Value>(matrix: &[Vec<V>], vector: &[V]) -> Vec<V> {
+    matrix.iter().map(|row| dot(row, vector)).collect()
+}
+
+/// Euclidean norm of a slice of values
+pub(crate) fn norm<V: Value>(values: &[V]) -> V {
+    dot(values, values).sqrt()
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_pivoting() {
+        let matrix = vec![vec![0.0_f64, 1.0_f64], vec![2.0_f64, 0.0_f64]];
+        let solution = solve(matrix, vec![3.0_f64, 4.0_f64]);
+        assert_eq!(solution, Some(vec![2.0_f64, 3.0_f64]));
+    }
+
+    #[test]
+    fn solve_singular() {
+        let matrix = vec![vec![1.0_f64, 2.0_f64], vec![2.0_f64, 4.0_f64]];
+        assert_eq!(solve(matrix, vec![1.0_f64, 2.0_f64]), None);
+    }
+
+    #[test]
+    fn least_squares_line() {
+        // fit y = a + b t through (0, 1), (1, 3), (2, 5)
+        let jacobian = [
+            vec![1.0_f64, 0.0_f64],
+            vec![1.0_f64, 1.0_f64],
+            vec![1.0_f64, 2.0_f64],
+        ];
+        let solution = least_squares(&jacobian, &[1.0_f64, 3.0_f64, 5.0_f64], 2, 0.0_f64);
+        assert_eq!(solution, Some(vec![1.0_f64, 2.0_f64]));
+    }
+}
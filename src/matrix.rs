@@ -0,0 +1,141 @@
+//! Differentiable determinant and inverse of [`nalgebra::DMatrix`] with dual elements
+//!
+//! Values are computed by [`nalgebra`] from the matrix of element values,
+//! and derivatives follow from the closed-form identities
+//! `d(det A) = tr(adj(A)·dA)` and `d(A⁻¹) = -A⁻¹·dA·A⁻¹`.
+//! Unlike propagating duals through elimination, no pivoting decisions are differentiated
+//!
+//!```
+//! use autodj::prelude::array::*;
+//! use autodj::matrix::{determinant, inverse};
+//! use nalgebra::dmatrix;
+//!
+//! let [a, b] = [2.0_f64, 3.0].into_variables();
+//! let matrix = dmatrix![a, b; b, a];
+//!
+//! // det = a² - b²
+//! let det = determinant(&matrix);
+//! assert_eq!(det.value(), &-5.0);
+//! assert_eq!(det.dual().as_ref(), &[4.0, -6.0]);
+//!
+//! let inv = inverse(&matrix).unwrap();
+//! // ∂(A⁻¹)₀₀/∂a = -(a² + b²) / (a² - b²)²
+//! assert!((inv[(0, 0)].value() + 0.4).abs() < 1e-15);
+//! assert!((inv[(0, 0)].dual().as_ref()[0] + 0.52).abs() < 1e-15);
+//! ```
+#![cfg(feature = "nalgebra")]
+
+use crate::fluid::{Dual, Value};
+use nalgebra::{DMatrix, RealField, Scalar};
+use num_traits::Zero;
+
+/// Matrix of element values
+fn values<D>(matrix: &DMatrix<D>) -> DMatrix<D::Value>
+where
+    D: Dual + Scalar,
+    D::Value: RealField,
+{
+    matrix.map(|elem| *elem.value())
+}
+
+/// Adjugate, i.e. transposed matrix of cofactors, defined for singular matrices as well
+fn adjugate<V: Value + RealField>(values: &DMatrix<V>) -> DMatrix<V> {
+    let size = values.nrows();
+    DMatrix::from_fn(size, size, |row, column| {
+        let minor = values
+            .clone()
+            .remove_row(column)
+            .remove_column(row)
+            .determinant();
+        if (row + column) % 2 == 0 {
+            minor
+        } else {
+            -minor
+        }
+    })
+}
+
+/// Differentiable determinant via the adjugate: `d(det A) = tr(adj(A)·dA)`
+///
+/// # Panics
+/// If the matrix is not square
+#[must_use]
+pub fn determinant<D>(matrix: &DMatrix<D>) -> D
+where
+    D: Dual + Scalar,
+    D::Value: RealField,
+{
+    assert!(
+        matrix.is_square(),
+        "unable to compute the determinant of a non-square matrix"
+    );
+    let values = values(matrix);
+    let grad = matrix
+        .iter()
+        .zip(adjugate(&values).transpose().iter())
+        .fold(D::Grad::zero(), |mut acc, (elem, &coefficient)| {
+            acc += elem.dual().clone() * coefficient;
+            acc
+        });
+    D::new(values.determinant(), grad)
+}
+
+/// Differentiable inverse: `d(A⁻¹) = -A⁻¹·dA·A⁻¹`.
+/// [`None`] for singular or non-square matrices
+#[must_use]
+pub fn inverse<D>(matrix: &DMatrix<D>) -> Option<DMatrix<D>>
+where
+    D: Dual + Scalar,
+    D::Value: RealField,
+{
+    let inverse = values(matrix).try_inverse()?;
+    let size = inverse.nrows();
+    Some(DMatrix::from_fn(size, size, |row, column| {
+        let grad = matrix.column_iter().zip(inverse.row_iter()).fold(
+            D::Grad::zero(),
+            |acc, (elems, right)| {
+                elems
+                    .iter()
+                    .zip(inverse.column_iter())
+                    .fold(acc, |mut acc, (elem, left)| {
+                        let left = left.get(row).copied().unwrap_or_else(D::Value::zero);
+                        let right = right.get(column).copied().unwrap_or_else(D::Value::zero);
+                        acc += elem.dual().clone() * -(left * right);
+                        acc
+                    })
+            },
+        );
+        let value = inverse
+            .get((row, column))
+            .copied()
+            .unwrap_or_else(D::Value::zero);
+        D::new(value, grad)
+    }))
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+    use crate::solid::single::{DualNumber, IntoVariable};
+    use nalgebra::dmatrix;
+    use std::prelude::v1::vec;
+
+    #[test]
+    fn singular_determinant() {
+        // d(det)/dp = tr(adj(A)·dA) is non-zero although det(A) = 0
+        let p: DualNumber<f64> = 1.0_f64.into_variable();
+        let one: DualNumber<f64> = 1.0_f64.into();
+        let matrix = dmatrix![p, one; one, one];
+        assert_eq!(determinant(&matrix).decompose(), (0.0_f64, 1.0_f64));
+        assert_eq!(inverse(&matrix), None);
+    }
+
+    #[test]
+    fn scalar_inverse() {
+        let p: DualNumber<f64> = 2.0_f64.into_variable();
+        let inv = inverse(&dmatrix![p]).and_then(|inv| inv.get(0).copied());
+        assert_eq!(inv.map(Dual::decompose), Some((0.5_f64, -0.25_f64)));
+        assert_eq!(determinant(&dmatrix![p]).decompose(), (2.0_f64, 1.0_f64));
+    }
+}
@@ -0,0 +1,356 @@
+//! First-order minimizers driven by automatic gradients
+//!
+//! - [`GradientDescent`]: steepest descent with backtracking
+//! - [`Lbfgs`]: limited-memory BFGS with a configurable number of stored corrections
+//!
+//! Step lengths are chosen by [`line_search`] routines.
+//! Constraints can be added to objectives as [`penalty`] terms.
+//!
+//!```
+//! use autodj::prelude::vector::*;
+//! use autodj::optim::Lbfgs;
+//!
+//! // Rosenbrock function
+//! let rosenbrock = |x: &[DualF64]| {
+//!     let a = DualF64::parameter(1.0) - x[0].clone();
+//!     let b = x[1].clone() - x[0].mul_impl(&x[0]);
+//!     a.mul_impl(&a) + b.mul_impl(&b) * 100.0
+//! };
+//!
+//! let report = Lbfgs::new().memory(7).minimize(&rosenbrock, &[-1.2, 1.0]);
+//! assert!(report.converged());
+//! assert!((report.solution()[0] - 1.0).abs() < 1e-6);
+//! assert!((report.solution()[1] - 1.0).abs() < 1e-6);
+//! ```
+#![cfg(feature = "alloc")]
+
+use crate::{
+    fluid::{Dual, Value},
+    function::DynDualFn,
+    linalg,
+    solid::vector::{DualNumber, IntoVariables},
+};
+use line_search::{along, Armijo, Wolfe};
+use std::{
+    collections::VecDeque,
+    fmt::{Display, Formatter, Result},
+    prelude::v1::Vec,
+};
+
+pub mod line_search;
+pub mod penalty;
+
+/// Objective value and its gradient of `point.len()` length
+fn value_and_gradient<V, F>(objective: &F, point: &[V]) -> (V, Vec<V>)
+where
+    V: Value,
+    F: DynDualFn<DualNumber<V>> + ?Sized,
+{
+    let (value, grad) = objective.eval(&point.to_vec().into_variables()).decompose();
+    let mut gradient = grad.as_ref().clone();
+    gradient.resize(point.len(), V::zero());
+    (value, gradient)
+}
+
+/// `point + step * direction`
+fn advance<V: Value>(point: &[V], direction: &[V], step: V) -> Vec<V> {
+    point
+        .iter()
+        .zip(direction)
+        .map(|(&coordinate, &increment)| coordinate + step * increment)
+        .collect()
+}
+
+/// Steepest descent with [`Armijo`] backtracking from a given learning rate
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GradientDescent<V: Value> {
+    /// maximum number of iterations
+    max_iterations: usize,
+    /// threshold for gradient norm
+    tolerance: V,
+    /// initial step length of every iteration
+    learning_rate: V,
+}
+
+impl<V: Value> Default for GradientDescent<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Value> GradientDescent<V> {
+    /// Construct with default settings
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            max_iterations: 1000,
+            tolerance: V::epsilon().sqrt(),
+            learning_rate: V::one(),
+        }
+    }
+
+    /// Set maximum number of iterations
+    #[must_use]
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Set convergence threshold for gradient norm
+    #[must_use]
+    pub fn tolerance(mut self, tolerance: V) -> Self {
+        self.tolerance = tolerance.abs();
+        self
+    }
+
+    /// Set initial step length of every iteration
+    #[must_use]
+    pub fn learning_rate(mut self, learning_rate: V) -> Self {
+        self.learning_rate = learning_rate.abs();
+        self
+    }
+
+    /// Minimize an objective function starting from a given point
+    pub fn minimize<F>(&self, objective: &F, initial: &[V]) -> MinimizeReport<V>
+    where
+        F: DynDualFn<DualNumber<V>> + ?Sized,
+    {
+        let mut solution = initial.to_vec();
+        let (mut value, mut gradient) = value_and_gradient(objective, &solution);
+        let mut iterations = 0;
+        while linalg::norm(&gradient) > self.tolerance && iterations < self.max_iterations {
+            let direction: Vec<V> = gradient.iter().map(|&deriv| -deriv).collect();
+            let Some(step) = Armijo::new()
+                .initial_step(self.learning_rate)
+                .search(along(objective, &solution, &direction))
+            else {
+                break;
+            };
+            solution = advance(&solution, &direction, step.step);
+            (value, gradient) = value_and_gradient(objective, &solution);
+            iterations += 1;
+        }
+        MinimizeReport::new(solution, value, &gradient, iterations, self.tolerance)
+    }
+}
+
+/// Limited-memory BFGS with [`Wolfe`] line search from the unit step
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Lbfgs<V: Value> {
+    /// maximum number of iterations
+    max_iterations: usize,
+    /// threshold for gradient norm
+    tolerance: V,
+    /// number of stored correction pairs
+    memory: usize,
+}
+
+impl<V: Value> Default for Lbfgs<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Value> Lbfgs<V> {
+    /// Construct with default settings
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            max_iterations: 1000,
+            tolerance: V::epsilon().sqrt(),
+            memory: 5,
+        }
+    }
+
+    /// Set maximum number of iterations
+    #[must_use]
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Set convergence threshold for gradient norm
+    #[must_use]
+    pub fn tolerance(mut self, tolerance: V) -> Self {
+        self.tolerance = tolerance.abs();
+        self
+    }
+
+    /// Set number of stored correction pairs. Zero memory reduces to steepest descent
+    #[must_use]
+    pub fn memory(mut self, memory: usize) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// Minimize an objective function starting from a given point
+    pub fn minimize<F>(&self, objective: &F, initial: &[V]) -> MinimizeReport<V>
+    where
+        F: DynDualFn<DualNumber<V>> + ?Sized,
+    {
+        let mut corrections: VecDeque<(Vec<V>, Vec<V>)> = VecDeque::with_capacity(self.memory);
+        let mut solution = initial.to_vec();
+        let (mut value, mut gradient) = value_and_gradient(objective, &solution);
+        let mut iterations = 0;
+        while linalg::norm(&gradient) > self.tolerance && iterations < self.max_iterations {
+            let mut direction = two_loop(&corrections, &gradient);
+            if linalg::dot(&gradient, &direction) >= V::zero() {
+                // not a descent direction: restart from steepest descent
+                corrections.clear();
+                direction = gradient.iter().map(|&deriv| -deriv).collect();
+            }
+            let Some(step) = Wolfe::new().search(along(objective, &solution, &direction)) else {
+                break;
+            };
+            let next = advance(&solution, &direction, step.step);
+            let (next_value, next_gradient) = value_and_gradient(objective, &next);
+            let shift = advance(&next, &solution, -V::one());
+            let change = advance(&next_gradient, &gradient, -V::one());
+            if self.memory > 0 && linalg::dot(&shift, &change) > V::zero() {
+                if corrections.len() == self.memory {
+                    let _oldest = corrections.pop_front();
+                }
+                corrections.push_back((shift, change));
+            }
+            (solution, value, gradient) = (next, next_value, next_gradient);
+            iterations += 1;
+        }
+        MinimizeReport::new(solution, value, &gradient, iterations, self.tolerance)
+    }
+}
+
+/// Two-loop recursion: descent direction `-H·gradient`
+/// with inverse Hessian approximation `H` built from correction pairs
+fn two_loop<V: Value>(corrections: &VecDeque<(Vec<V>, Vec<V>)>, gradient: &[V]) -> Vec<V> {
+    let mut direction: Vec<V> = gradient.iter().map(|&deriv| -deriv).collect();
+    let mut alphas = Vec::with_capacity(corrections.len());
+    for (shift, change) in corrections.iter().rev() {
+        let alpha = linalg::dot(shift, &direction) / linalg::dot(shift, change);
+        direction = advance(&direction, change, -alpha);
+        alphas.push(alpha);
+    }
+    if let Some((shift, change)) = corrections.back() {
+        let scale = linalg::dot(shift, change) / linalg::dot(change, change);
+        for elem in &mut direction {
+            *elem *= scale;
+        }
+    }
+    for ((shift, change), alpha) in corrections.iter().zip(alphas.into_iter().rev()) {
+        let beta = linalg::dot(change, &direction) / linalg::dot(shift, change);
+        direction = advance(&direction, shift, alpha - beta);
+    }
+    direction
+}
+
+/// Outcome of minimization
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinimizeReport<V: Value> {
+    /// last iterate
+    solution: Vec<V>,
+    /// objective value at the last iterate
+    value: V,
+    /// Euclidean norm of the gradient at the last iterate
+    gradient_norm: V,
+    /// number of performed iterations
+    iterations: usize,
+    /// whether tolerance has been reached
+    converged: bool,
+}
+
+impl<V: Value> MinimizeReport<V> {
+    /// Assemble from the last iterate
+    fn new(solution: Vec<V>, value: V, gradient: &[V], iterations: usize, tolerance: V) -> Self {
+        let gradient_norm = linalg::norm(gradient);
+        Self {
+            solution,
+            value,
+            gradient_norm,
+            iterations,
+            converged: gradient_norm <= tolerance,
+        }
+    }
+
+    /// Last iterate
+    #[must_use]
+    pub fn solution(&self) -> &[V] {
+        &self.solution
+    }
+
+    /// Take the last iterate out of the report
+    #[must_use]
+    pub fn into_solution(self) -> Vec<V> {
+        self.solution
+    }
+
+    /// Objective value at the last iterate
+    #[must_use]
+    pub fn value(&self) -> V {
+        self.value
+    }
+
+    /// Euclidean norm of the gradient at the last iterate
+    #[must_use]
+    pub fn gradient_norm(&self) -> V {
+        self.gradient_norm
+    }
+
+    /// Number of performed iterations
+    #[must_use]
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Whether tolerance has been reached
+    #[must_use]
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+}
+
+impl<V: Value + Display> Display for MinimizeReport<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let status = if self.converged {
+            "converged"
+        } else {
+            "not converged"
+        };
+        write!(
+            f,
+            "{status} after {} iterations, f = {}, |g| = {}",
+            self.iterations, self.value, self.gradient_norm
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ill-conditioned quadratic `x² + 10 y²` with minimum at `(1, -2)`
+    fn quadratic(x: &[DualNumber<f64>]) -> DualNumber<f64> {
+        x.iter()
+            .zip([(1.0_f64, 1.0_f64), (-2.0_f64, 10.0_f64)])
+            .fold(
+                DualNumber::parameter(0.0_f64),
+                |acc, (x, (center, weight))| {
+                    let shifted = x.clone() - center;
+                    acc + shifted.mul_impl(&shifted) * weight
+                },
+            )
+    }
+
+    #[test]
+    fn gradient_descent_quadratic() {
+        let report = GradientDescent::new().minimize(&quadratic, &[0.0_f64, 0.0_f64]);
+        assert!(report.converged());
+        assert!(report.value() < 1e-12_f64);
+    }
+
+    #[test]
+    fn lbfgs_beats_gradient_descent() {
+        let descent = GradientDescent::new().minimize(&quadratic, &[0.0_f64, 0.0_f64]);
+        let lbfgs = Lbfgs::new().minimize(&quadratic, &[0.0_f64, 0.0_f64]);
+        assert!(lbfgs.converged());
+        assert!(lbfgs.iterations() < descent.iterations());
+    }
+}
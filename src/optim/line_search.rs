@@ -0,0 +1,318 @@
+//! Line searches over `φ(α) = f(x + α·d)`
+//!
+//! A single evaluation of `φ` over [`single::DualNumber`] gives both `φ(α)` and `φ′(α)`,
+//! so no extra evaluations are spent on directional derivatives.
+//! [`along`] turns an objective over [`vector::DualNumber`] into such `φ`.
+//!
+//!```
+//! use autodj::prelude::vector::*;
+//! use autodj::optim::line_search::{along, Wolfe};
+//!
+//! let objective = |x: &[DualF64]| x[0].mul_impl(&x[0]) + x[1].mul_impl(&x[1]);
+//! let phi = along(&objective, &[1.0, 1.0], &[-1.0, 0.0]);
+//!
+//! let step = Wolfe::new().search(phi).unwrap();
+//! assert!(step.value < 2.0);
+//! assert!(step.slope.abs() <= 0.9 * 2.0);
+//! ```
+
+use crate::{
+    fluid::{half, Dual, Value},
+    function::DynDualFn,
+    solid::{
+        single::{self, IntoVariable},
+        vector,
+    },
+};
+use std::prelude::v1::Vec;
+
+/// Accepted step of a line search
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Step<V: Value> {
+    /// step length `α`
+    pub step: V,
+    /// `φ(α)`
+    pub value: V,
+    /// `φ′(α)`
+    pub slope: V,
+}
+
+/// Evaluate `φ` at a given step length
+fn probe<V, Phi>(phi: &Phi, step: V) -> Step<V>
+where
+    V: Value,
+    Phi: Fn(single::DualNumber<V>) -> single::DualNumber<V>,
+{
+    let (value, slope) = phi(step.into_variable()).decompose();
+    Step { step, value, slope }
+}
+
+/// `φ(α) = f(x + α·d)` for an objective over [`vector::DualNumber`]
+pub fn along<'a, V, F>(
+    objective: &'a F,
+    point: &'a [V],
+    direction: &'a [V],
+) -> impl Fn(single::DualNumber<V>) -> single::DualNumber<V> + 'a
+where
+    V: Value,
+    F: DynDualFn<vector::DualNumber<V>> + ?Sized,
+{
+    move |step| {
+        let (step, seed) = step.decompose();
+        let shifted: Vec<vector::DualNumber<V>> = point
+            .iter()
+            .zip(direction)
+            .map(|(&coordinate, &increment)| {
+                vector::DualNumber::new(coordinate + step * increment, [increment * seed].into())
+            })
+            .collect();
+        let (value, grad) = objective.eval(&shifted).decompose();
+        single::DualNumber::new(
+            value,
+            grad.as_ref().first().copied().unwrap_or_else(V::zero),
+        )
+    }
+}
+
+/// Backtracking until the Armijo sufficient decrease condition
+/// `φ(α) ≤ φ(0) + c₁·α·φ′(0)` holds
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Armijo<V: Value> {
+    /// first trial step
+    initial_step: V,
+    /// factor of step reduction
+    shrink: V,
+    /// `c₁`
+    sufficient_decrease: V,
+    /// maximum number of trial steps
+    max_iterations: usize,
+}
+
+impl<V: Value> Default for Armijo<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Value> Armijo<V> {
+    /// Unit initial step, halving, `c₁ = √ε`
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            initial_step: V::one(),
+            shrink: half(),
+            sufficient_decrease: V::epsilon().sqrt(),
+            max_iterations: 60,
+        }
+    }
+
+    /// Set the first trial step
+    #[must_use]
+    pub fn initial_step(mut self, initial_step: V) -> Self {
+        self.initial_step = initial_step.abs();
+        self
+    }
+
+    /// Set the factor of step reduction, expected within `(0, 1)`
+    #[must_use]
+    pub fn shrink(mut self, shrink: V) -> Self {
+        self.shrink = shrink.abs();
+        self
+    }
+
+    /// Set `c₁` of the sufficient decrease condition
+    #[must_use]
+    pub fn sufficient_decrease(mut self, sufficient_decrease: V) -> Self {
+        self.sufficient_decrease = sufficient_decrease.abs();
+        self
+    }
+
+    /// Set maximum number of trial steps
+    #[must_use]
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Search for an acceptable step.
+    /// Returns [`None`] if `d` is not a descent direction or no trial step is accepted
+    pub fn search<Phi>(&self, phi: Phi) -> Option<Step<V>>
+    where
+        Phi: Fn(single::DualNumber<V>) -> single::DualNumber<V>,
+    {
+        let origin = probe(&phi, V::zero());
+        if origin.slope >= V::zero() {
+            return None;
+        }
+        let mut step = self.initial_step;
+        for _ in 0..self.max_iterations {
+            let trial = probe(&phi, step);
+            if trial.value <= origin.value + self.sufficient_decrease * step * origin.slope {
+                return Some(trial);
+            }
+            step *= self.shrink;
+        }
+        None
+    }
+}
+
+/// Bracketing search for a step satisfying the strong Wolfe conditions:
+/// sufficient decrease and `|φ′(α)| ≤ c₂·|φ′(0)|`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Wolfe<V: Value> {
+    /// first trial step
+    initial_step: V,
+    /// `c₁`
+    sufficient_decrease: V,
+    /// `c₂`
+    curvature: V,
+    /// maximum number of trial steps in each of bracketing and zooming
+    max_iterations: usize,
+}
+
+impl<V: Value> Default for Wolfe<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Value> Wolfe<V> {
+    /// Unit initial step, `c₁ = √ε`, `c₂ = 0.9`
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            initial_step: V::one(),
+            sufficient_decrease: V::epsilon().sqrt(),
+            curvature: V::from(0.9_f64).unwrap_or_else(half),
+            max_iterations: 60,
+        }
+    }
+
+    /// Set the first trial step
+    #[must_use]
+    pub fn initial_step(mut self, initial_step: V) -> Self {
+        self.initial_step = initial_step.abs();
+        self
+    }
+
+    /// Set `c₁` of the sufficient decrease condition
+    #[must_use]
+    pub fn sufficient_decrease(mut self, sufficient_decrease: V) -> Self {
+        self.sufficient_decrease = sufficient_decrease.abs();
+        self
+    }
+
+    /// Set `c₂` of the curvature condition, expected within `(c₁, 1)`
+    #[must_use]
+    pub fn curvature(mut self, curvature: V) -> Self {
+        self.curvature = curvature.abs();
+        self
+    }
+
+    /// Set maximum number of trial steps in each of bracketing and zooming
+    #[must_use]
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Search for an acceptable step.
+    /// Returns [`None`] if `d` is not a descent direction or no trial step is accepted
+    pub fn search<Phi>(&self, phi: Phi) -> Option<Step<V>>
+    where
+        Phi: Fn(single::DualNumber<V>) -> single::DualNumber<V>,
+    {
+        let origin = probe(&phi, V::zero());
+        if origin.slope >= V::zero() {
+            return None;
+        }
+        let two = V::one() + V::one();
+        let mut previous = origin;
+        let mut step = self.initial_step;
+        for iteration in 0..self.max_iterations {
+            let trial = probe(&phi, step);
+            if !self.decreases(&origin, &trial) || (iteration > 0 && trial.value >= previous.value)
+            {
+                return self.zoom(&phi, &origin, previous, trial);
+            }
+            if self.flattens(&origin, &trial) {
+                return Some(trial);
+            }
+            if trial.slope >= V::zero() {
+                return self.zoom(&phi, &origin, trial, previous);
+            }
+            previous = trial;
+            step *= two;
+        }
+        None
+    }
+
+    /// Sufficient decrease condition
+    fn decreases(&self, origin: &Step<V>, trial: &Step<V>) -> bool {
+        trial.value <= origin.value + self.sufficient_decrease * trial.step * origin.slope
+    }
+
+    /// Strong curvature condition
+    fn flattens(&self, origin: &Step<V>, trial: &Step<V>) -> bool {
+        trial.slope.abs() <= -self.curvature * origin.slope
+    }
+
+    /// Bisect a bracket with the lower value at `low` until the strong Wolfe conditions hold
+    fn zoom<Phi>(
+        &self,
+        phi: &Phi,
+        origin: &Step<V>,
+        mut low: Step<V>,
+        mut high: Step<V>,
+    ) -> Option<Step<V>>
+    where
+        Phi: Fn(single::DualNumber<V>) -> single::DualNumber<V>,
+    {
+        for _ in 0..self.max_iterations {
+            let trial = probe(phi, (low.step + high.step) * half());
+            if !self.decreases(origin, &trial) || trial.value >= low.value {
+                high = trial;
+            } else {
+                if self.flattens(origin, &trial) {
+                    return Some(trial);
+                }
+                if trial.slope * (high.step - low.step) >= V::zero() {
+                    high = low;
+                }
+                low = trial;
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+
+    /// `φ(α) = (α - 3)²`
+    fn parabola(step: single::DualNumber<f64>) -> single::DualNumber<f64> {
+        let shifted = step - 3.0_f64;
+        shifted * shifted
+    }
+
+    #[test]
+    fn armijo_accepts_unit_step() {
+        let step = Armijo::new().search(parabola);
+        assert_eq!(step.map(|step| step.step), Some(1.0_f64));
+    }
+
+    #[test]
+    fn wolfe_brackets_minimum() {
+        let step = Wolfe::new().curvature(0.1_f64).search(parabola);
+        assert_eq!(step.map(|step| step.step), Some(3.0_f64));
+    }
+
+    #[test]
+    fn ascent_direction() {
+        let ascent = |step: single::DualNumber<f64>| -parabola(step);
+        assert_eq!(Armijo::new().search(ascent), None);
+        assert_eq!(Wolfe::new().search(ascent), None);
+    }
+}
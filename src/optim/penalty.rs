@@ -0,0 +1,141 @@
+//! Differentiable penalty terms for constrained problems
+//!
+//! Equality constraints are given as `c(x) = 0`, inequality ones as `g(x) ≤ 0`.
+//! Each term is a function of a constraint value over any [`Dual`],
+//! so it can be added to an objective and minimized by an unconstrained method.
+//!
+//!```
+//! use autodj::prelude::vector::*;
+//! use autodj::optim::{penalty, Lbfgs};
+//!
+//! // minimize x² + y² subject to x + y = 1
+//! let (mut multiplier, weight) = (0.0, 10.0);
+//! let mut point = vec![0.0, 0.0];
+//! for _ in 0..10 {
+//!     let lagrangian = |x: &[DualF64]| {
+//!         let constraint = x[0].add_impl(&x[1]) - 1.0;
+//!         x[0].mul_impl(&x[0])
+//!             + x[1].mul_impl(&x[1])
+//!             + penalty::augmented_lagrangian(&constraint, multiplier, weight)
+//!     };
+//!     point = Lbfgs::new().minimize(&lagrangian, &point).into_solution();
+//!     multiplier = penalty::update_multiplier(multiplier, point[0] + point[1] - 1.0, weight);
+//! }
+//! assert!((point[0] - 0.5).abs() < 1e-6 && (point[1] - 0.5).abs() < 1e-6);
+//! assert!((multiplier + 1.0).abs() < 1e-6);
+//! ```
+
+use crate::fluid::{half, Dual, Value};
+use num_traits::{real::Real, Zero};
+
+/// Quadratic penalty `½·weight·c²` of an equality constraint `c = 0`
+#[must_use]
+pub fn quadratic<D: Dual>(equality: &D, weight: D::Value) -> D {
+    equality.chain(|&value| (half::<D::Value>() * weight * value * value, weight * value))
+}
+
+/// Quadratic penalty `½·weight·max(0, g)²` of an inequality constraint `g ≤ 0`
+#[must_use]
+pub fn quadratic_inequality<D: Dual>(inequality: &D, weight: D::Value) -> D {
+    inequality.chain(|&value| {
+        let violation = value.max(D::Value::zero());
+        (
+            half::<D::Value>() * weight * violation * violation,
+            weight * violation,
+        )
+    })
+}
+
+/// Logarithmic barrier `-weight·ln(-g)` of an inequality constraint `g ≤ 0`.
+/// [`None`] outside of the strictly feasible region
+#[must_use]
+pub fn log_barrier<D: Dual>(inequality: &D, weight: D::Value) -> Option<D> {
+    (*inequality.value() < D::Value::zero())
+        .then(|| inequality.chain(|&value| (-weight * (-value).ln(), -weight / value)))
+}
+
+/// Augmented Lagrangian term `λ·c + ½·weight·c²` of an equality constraint `c = 0`
+#[must_use]
+pub fn augmented_lagrangian<D: Dual>(equality: &D, multiplier: D::Value, weight: D::Value) -> D {
+    equality.chain(|&value| {
+        (
+            multiplier * value + half::<D::Value>() * weight * value * value,
+            multiplier + weight * value,
+        )
+    })
+}
+
+/// Augmented Lagrangian term `(max(0, λ + weight·g)² - λ²) / (2·weight)`
+/// of an inequality constraint `g ≤ 0`
+#[must_use]
+pub fn augmented_lagrangian_inequality<D: Dual>(
+    inequality: &D,
+    multiplier: D::Value,
+    weight: D::Value,
+) -> D {
+    inequality.chain(|&value| {
+        let shifted = (multiplier + weight * value).max(D::Value::zero());
+        (
+            half::<D::Value>() * (shifted * shifted - multiplier * multiplier) / weight,
+            shifted,
+        )
+    })
+}
+
+/// First-order multiplier update `λ + weight·c` after minimizing with [`augmented_lagrangian`]
+#[must_use]
+pub fn update_multiplier<V: Value>(multiplier: V, equality: V, weight: V) -> V {
+    multiplier + weight * equality
+}
+
+/// First-order multiplier update `max(0, λ + weight·g)`
+/// after minimizing with [`augmented_lagrangian_inequality`]
+#[must_use]
+pub fn update_inequality_multiplier<V: Value>(multiplier: V, inequality: V, weight: V) -> V {
+    (multiplier + weight * inequality).max(V::zero())
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+    use crate::solid::single::{DualNumber, IntoVariable};
+
+    #[test]
+    fn inactive_inequality() {
+        let feasible: DualNumber<f64> = (-1.0_f64).into_variable();
+        assert_eq!(
+            quadratic_inequality(&feasible, 10.0_f64).decompose(),
+            (0.0_f64, 0.0_f64)
+        );
+        assert_eq!(
+            augmented_lagrangian_inequality(&feasible, 0.0_f64, 10.0_f64).decompose(),
+            (0.0_f64, 0.0_f64)
+        );
+    }
+
+    #[test]
+    fn barrier_domain() {
+        let feasible: DualNumber<f64> = (-1.0_f64).into_variable();
+        assert_eq!(
+            log_barrier(&feasible, 2.0_f64).map(Dual::decompose),
+            Some((0.0_f64, 2.0_f64))
+        );
+        let boundary: DualNumber<f64> = 0.0_f64.into_variable();
+        assert_eq!(log_barrier(&boundary, 2.0_f64), None);
+    }
+
+    #[test]
+    fn equality_terms() {
+        let violated: DualNumber<f64> = 2.0_f64.into_variable();
+        assert_eq!(
+            quadratic(&violated, 3.0_f64).decompose(),
+            (6.0_f64, 6.0_f64)
+        );
+        assert_eq!(
+            augmented_lagrangian(&violated, 1.0_f64, 3.0_f64).decompose(),
+            (8.0_f64, 7.0_f64)
+        );
+        assert_eq!(update_multiplier(1.0_f64, 2.0_f64, 3.0_f64), 7.0_f64);
+    }
+}
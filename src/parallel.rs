@@ -0,0 +1,95 @@
+//! Multi-threaded forward-mode sweeps backed by [`rayon`]
+//!
+//! Seed directions are independent of each other,
+//! so they are split into contiguous blocks evaluated on separate threads
+//!
+//!```
+//! use autodj::prelude::vector::*;
+//! use autodj::parallel::parallel_jacobian;
+//!
+//! let func = |x: &[DualF64]| vec![x[0].mul_impl(&x[1]), x[1].add_impl(&x[2])];
+//! let jacobian = parallel_jacobian(&func, &[2.0, 3.0, 4.0]);
+//! assert_eq!(jacobian, [3.0, 2.0, 0.0, 0.0, 1.0, 1.0]);
+//! ```
+#![cfg(feature = "rayon")]
+
+use crate::{
+    fluid::{Dual, Value},
+    function::VectorDualFn,
+    solid::vector::{DualNumber, IntoVariables},
+};
+use rayon::prelude::*;
+use std::prelude::v1::{vec, Vec};
+
+/// Row-major dense Jacobian: a row per output, a column per variable.
+///
+/// Every thread evaluates `func` once with gradients restricted to its block of variables
+pub fn parallel_jacobian<V, F>(func: &F, point: &[V]) -> Vec<V>
+where
+    V: Value + Send + Sync,
+    F: VectorDualFn<DualNumber<V>> + Sync + ?Sized,
+{
+    let columns = point.len();
+    let width = columns.div_ceil(rayon::current_num_threads()).max(1);
+    let blocks: Vec<(usize, Vec<Vec<V>>)> = (0..columns.div_ceil(width))
+        .into_par_iter()
+        .map(|block| {
+            let start = block * width;
+            let end = (start + width).min(columns);
+            let seeds = (0..columns).map(|column| {
+                let mut seed = Vec::new();
+                if (start..end).contains(&column) {
+                    seed.resize(end - start, V::zero());
+                    if let Some(deriv) = seed.get_mut(column - start) {
+                        *deriv = V::one();
+                    }
+                }
+                seed
+            });
+            let rows = func
+                .eval(&point.to_vec().into_variables_with_seeds(seeds))
+                .into_iter()
+                .map(|output| {
+                    let mut row = output.dual().as_ref().clone();
+                    row.resize(end - start, V::zero());
+                    row
+                })
+                .collect();
+            (start, rows)
+        })
+        .collect();
+
+    let rows = blocks.first().map_or(0, |(_, block)| block.len());
+    let mut jacobian = vec![V::zero(); rows * columns];
+    for (start, block) in blocks {
+        for (row, derivs) in jacobian.chunks_exact_mut(columns).zip(block) {
+            for (elem, deriv) in row.iter_mut().skip(start).zip(derivs) {
+                *elem = deriv;
+            }
+        }
+    }
+    jacobian
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+    use crate::solver::evaluate;
+
+    #[test]
+    fn matches_sequential_jacobian() {
+        // every output couples neighbouring variables
+        let func = |x: &[DualNumber<f64>]| -> Vec<DualNumber<f64>> {
+            x.windows(2)
+                .map(|pair| match pair {
+                    [left, right] => left.mul_impl(right).sin(),
+                    _ => DualNumber::parameter(0.0_f64),
+                })
+                .collect()
+        };
+        let point: Vec<f64> = (0..50_u32).map(f64::from).collect();
+        let (_, rows) = evaluate(&func, &point, point.len());
+        assert_eq!(parallel_jacobian(&func, &point), rows.concat());
+    }
+}
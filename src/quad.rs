@@ -0,0 +1,164 @@
+//! Differentiable numerical quadrature
+//!
+//! Integrands map a dual abscissa to a dual value, so an integral is differentiated
+//! with respect to whatever its integrand captures, as well as to its limits.
+//! Constant limits are given as [`Dual::parameter`]
+//!
+//!```
+//! use autodj::prelude::single::*;
+//! use autodj::quad::gauss_legendre;
+//!
+//! // ∫₀¹ exp(p·x) dx = (exp(p) - 1) / p
+//! let p = 2.0.into_variable();
+//! let integral = gauss_legendre(&|x: DualF64| (p * x).exp(), &0.0.into(), &1.0.into(), 8);
+//!
+//! let (value, deriv) = integral.decompose();
+//! let exact = (2.0_f64.exp() - 1.0) / 2.0;
+//! assert!((value - exact).abs() < 1e-12);
+//! assert!((deriv - (2.0_f64.exp() - exact) / 2.0).abs() < 1e-12);
+//! ```
+#![cfg(feature = "alloc")]
+
+use crate::{
+    fluid::{half, Dual, Value},
+    function::ScalarDualFn,
+};
+use num_traits::{real::Real, NumCast, Zero};
+use std::prelude::v1::Vec;
+
+/// Count as a value
+fn count<V: Value>(count: usize) -> V {
+    NumCast::from(count).unwrap_or_else(V::zero)
+}
+
+/// `∑ wᵢ·f(a + (b - a)·tᵢ)·(b - a)` over nodes `tᵢ ∈ [0, 1]` with weights `wᵢ`
+fn weighted_sum<D, F, Nodes>(integrand: &F, lower: &D, upper: &D, nodes: Nodes) -> D
+where
+    D: Dual,
+    F: ScalarDualFn<D> + ?Sized,
+    Nodes: IntoIterator<Item = (D::Value, D::Value)>,
+{
+    let width = upper.sub_impl(lower);
+    nodes
+        .into_iter()
+        .fold(D::parameter(D::Value::zero()), |acc, (node, weight)| {
+            let abscissa = lower.add_impl(&width.mul_impl(&D::parameter(node)));
+            acc.add_impl(&integrand.eval(abscissa).mul_impl(&D::parameter(weight)))
+        })
+        .mul_impl(&width)
+}
+
+/// Composite trapezoidal rule over `intervals` equal subintervals.
+/// Zero intervals are treated as one
+#[must_use]
+pub fn trapezoid<D, F>(integrand: &F, lower: &D, upper: &D, intervals: usize) -> D
+where
+    D: Dual,
+    F: ScalarDualFn<D> + ?Sized,
+{
+    let intervals = intervals.max(1);
+    let step = count::<D::Value>(intervals).recip();
+    let nodes = (0..=intervals).map(|index| {
+        let weight = if index == 0 || index == intervals {
+            half::<D::Value>() * step
+        } else {
+            step
+        };
+        (count::<D::Value>(index) * step, weight)
+    });
+    weighted_sum(integrand, lower, upper, nodes)
+}
+
+/// Gauss-Legendre rule with `points` nodes, exact for polynomials of degree up to `2·points - 1`.
+/// Zero points are treated as one
+#[must_use]
+pub fn gauss_legendre<D, F>(integrand: &F, lower: &D, upper: &D, points: usize) -> D
+where
+    D: Dual,
+    F: ScalarDualFn<D> + ?Sized,
+{
+    weighted_sum(integrand, lower, upper, legendre_nodes(points.max(1)))
+}
+
+/// Gauss-Legendre nodes and weights mapped onto `[0, 1]`,
+/// found by Newton iterations on Legendre polynomials
+fn legendre_nodes<V: Value>(points: usize) -> Vec<(V, V)> {
+    let one = V::one();
+    let order = count::<V>(points);
+    let pi: V = NumCast::from(core::f64::consts::PI).unwrap_or_else(V::zero);
+    let quarter = half::<V>() * half();
+    let mut nodes = Vec::with_capacity(points);
+    for root in 1..=points.div_ceil(2) {
+        let mut node = (pi * (count::<V>(root) - quarter) / (order + half())).cos();
+        let mut slope = one;
+        for _ in 0..100_usize {
+            // (value, previous) of Legendre polynomials by the three-term recurrence
+            let (value, previous) =
+                (1..=points).fold((one, V::zero()), |(value, previous), degree| {
+                    let degree = count::<V>(degree);
+                    (
+                        ((degree + degree - one) * node * value - (degree - one) * previous)
+                            / degree,
+                        value,
+                    )
+                });
+            slope = order * (node * value - previous) / (node * node - one);
+            let step = value / slope;
+            node -= step;
+            if step.abs() <= V::epsilon() {
+                break;
+            }
+        }
+        let weight = (one - node * node).recip() / (slope * slope);
+        let offset = half::<V>() * node;
+        nodes.push((half::<V>() - offset, weight));
+        if root + root <= points {
+            nodes.push((half::<V>() + offset, weight));
+        }
+    }
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solid::single::{DualNumber, IntoVariable};
+
+    #[test]
+    fn trapezoid_is_exact_for_lines() {
+        let slope: DualNumber<f64> = 3.0_f64.into_variable();
+        let integral = trapezoid(
+            &|x: DualNumber<f64>| slope * x,
+            &0.0_f64.into(),
+            &2.0_f64.into(),
+            1,
+        );
+        // ∫₀² s·x dx = 2·s
+        assert!((integral.value() - 6.0_f64).abs() < 1e-12_f64);
+        assert!((integral.dual() - 2.0_f64).abs() < 1e-12_f64);
+    }
+
+    #[test]
+    fn gauss_legendre_upper_limit() {
+        // ∂/∂b ∫₀ᵇ x⁵ dx = b⁵, exact with three points
+        let upper: DualNumber<f64> = 2.0_f64.into_variable();
+        let integral = gauss_legendre(
+            &|x: DualNumber<f64>| x.powf(5.0_f64),
+            &0.0_f64.into(),
+            &upper,
+            3,
+        );
+        assert!((integral.value() - 64.0_f64 / 6.0_f64).abs() < 1e-12_f64);
+        assert!((integral.dual() - 32.0_f64).abs() < 1e-12_f64);
+    }
+
+    #[test]
+    fn weights_sum_to_unity() {
+        for points in 1..=9 {
+            let nodes = legendre_nodes::<f64>(points);
+            assert_eq!(nodes.len(), points);
+            let total: f64 = nodes.iter().map(|&(_, weight)| weight).sum();
+            assert!((total - 1.0_f64).abs() < 1e-14_f64);
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! Differentiable reductions over slices of dual numbers
+//!
+//!```
+//! # #[cfg(feature = "alloc")] {
+//! use autodj::prelude::vector::*;
+//! use autodj::reduce::{dot, norm};
+//!
+//! let x: Vec<DualF64> = vec![3.0, 4.0].into_variables();
+//! let length = norm(&x);
+//! assert_eq!(length.value(), &5.0);
+//! let gradient = length.dual().as_ref();
+//! assert!((gradient[0] - 0.6).abs() < 1e-15 && (gradient[1] - 0.8).abs() < 1e-15);
+//!
+//! let weights: Vec<DualF64> = vec![2.0.into(), 1.0.into()];
+//! let weighted = dot(&weights, &x);
+//! assert_eq!(weighted.value(), &10.0);
+//! assert_eq!(weighted.dual().as_ref(), &[2.0, 1.0]);
+//! # }
+//! ```
+//!
+//! Statistical reductions are not defined for empty slices
+//!
+//!```
+//! # #[cfg(feature = "alloc")] {
+//! use autodj::prelude::vector::*;
+//! use autodj::reduce::{mean, variance};
+//!
+//! let x: Vec<DualF64> = vec![1.0, 2.0, 3.0, 6.0].into_variables();
+//! let average = mean(&x).unwrap();
+//! assert_eq!(average.value(), &3.0);
+//! assert_eq!(average.dual().as_ref(), &[0.25; 4]);
+//!
+//! let spread = variance(&x).unwrap();
+//! assert_eq!(spread.value(), &3.5);
+//! assert_eq!(spread.dual().as_ref(), &[-1.0, -0.5, 0.0, 1.5]);
+//!
+//! assert_eq!(mean::<DualF64>(&[]), None);
+//! # }
+//! ```
+
+use crate::fluid::{half, Dual};
+use num_traits::{real::Real, One, Zero};
+use std::borrow::Borrow;
+
+/// Differentiable dot product `∑ a_i·b_i`.
+///
+/// Excess elements of the longer slice are ignored
+#[must_use]
+pub fn dot<D: Dual>(a: &[D], b: &[D]) -> D {
+    a.iter()
+        .zip(b)
+        .fold(D::parameter(D::Value::zero()), |acc, (a, b)| {
+            acc.add_impl(&a.mul_impl(b))
+        })
+}
+
+/// Sum `∑ x_i` with Kahan compensation.
+///
+/// Dual arithmetic acts on every component separately,
+/// so the rounding error is compensated in the value and in each derivative alike
+#[must_use]
+pub fn sum_compensated<D: Dual>(x: &[D]) -> D {
+    let zero = D::parameter(D::Value::zero());
+    let (sum, _) = x
+        .iter()
+        .fold((zero.clone(), zero), |(sum, compensation), x| {
+            let corrected = x.sub_impl(&compensation);
+            let next = sum.add_impl(&corrected);
+            let compensation = next.sub_impl(&sum).sub_impl(&corrected);
+            (next, compensation)
+        });
+    sum
+}
+
+/// Differentiable Euclidean norm `√(∑ x_i²)`.
+///
+/// Derivatives at zero norm are taken to be zero
+#[must_use]
+pub fn norm<D: Dual>(x: &[D]) -> D {
+    dot(x, x).chain(|&squared| {
+        if squared.is_zero() {
+            (D::Value::zero(), D::Value::zero())
+        } else {
+            let value = squared.sqrt();
+            (value, half::<D::Value>() / value)
+        }
+    })
+}
+
+/// Differentiable arithmetic mean. [`None`] for empty slices
+#[must_use]
+pub fn mean<D: Dual>(x: &[D]) -> Option<D> {
+    mean_of(x.iter())
+}
+
+/// Arithmetic mean of borrowed or owned terms without collecting them
+fn mean_of<D: Dual, T: Borrow<D>, I: Iterator<Item = T>>(terms: I) -> Option<D> {
+    let (sum, count) = terms.fold(
+        (D::parameter(D::Value::zero()), D::Value::zero()),
+        |(sum, count), x| (sum.add_impl(x.borrow()), count + D::Value::one()),
+    );
+    (!count.is_zero()).then(|| {
+        let scale = count.recip();
+        sum.chain(|&sum| (sum * scale, scale))
+    })
+}
+
+/// Differentiable population variance `∑ (x_i - mean)² / n`. [`None`] for empty slices
+#[must_use]
+pub fn variance<D: Dual>(x: &[D]) -> Option<D> {
+    let average = mean(x)?;
+    mean_of(x.iter().map(|x| {
+        let deviation = x.sub_impl(&average);
+        deviation.mul_impl(&deviation)
+    }))
+}
+
+/// Differentiable population standard deviation `√variance`. [`None`] for empty slices.
+///
+/// Derivatives at zero deviation are taken to be zero
+#[must_use]
+pub fn std<D: Dual>(x: &[D]) -> Option<D> {
+    variance(x).map(|variance| {
+        variance.chain(|&variance| {
+            if variance.is_zero() {
+                (D::Value::zero(), D::Value::zero())
+            } else {
+                let value = variance.sqrt();
+                (value, half::<D::Value>() / value)
+            }
+        })
+    })
+}
+
+/// Stable sort by value in the total order of [`Dual::total_cmp`]
+#[cfg(feature = "alloc")]
+pub fn sort_by_value<D: Dual>(x: &mut [D])
+where
+    D::Value: num_traits::float::TotalOrder,
+{
+    x.sort_by(Dual::total_cmp);
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+    use crate::solid::array::{DualNumber, IntoVariables};
+
+    #[test]
+    fn norm_at_zero() {
+        let x: [DualNumber<f64, 2>; 2] = [0.0_f64, 0.0_f64].into_variables();
+        let length = norm(&x);
+        assert_eq!(length.value(), &0.0_f64);
+        assert_eq!(length.dual().as_ref(), &[0.0_f64, 0.0_f64]);
+    }
+
+    #[test]
+    fn std_of_constant() {
+        let x: [DualNumber<f64, 2>; 2] = [1.0_f64, 1.0_f64].into_variables();
+        let deviation = std(&x).unwrap_or_else(|| panic!("slice is not empty"));
+        assert_eq!(deviation.value(), &0.0_f64);
+        assert_eq!(deviation.dual().as_ref(), &[0.0_f64, 0.0_f64]);
+    }
+
+    #[test]
+    fn compensated_sum_of_tiny_terms() {
+        let [x, y]: [DualNumber<f64, 2>; 2] = [1.0_f64, 1.0_f64].into_variables();
+        let tiny = y * 1e-16_f64;
+        let mut terms = [tiny; 11];
+        if let Some(first) = terms.first_mut() {
+            *first = x;
+        }
+
+        let naive = terms
+            .iter()
+            .fold(DualNumber::parameter(0.0_f64), |acc, term| acc + *term);
+        assert_eq!(naive.value(), &1.0_f64);
+
+        let total = sum_compensated(&terms);
+        assert!((total.value() - (1.0_f64 + 1e-15_f64)).abs() < f64::EPSILON / 4.0_f64);
+        assert!((total.dual().as_ref()[1] - 1e-15_f64).abs() < 1e-30_f64);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn sort_with_nan() {
+        let mut x: [DualNumber<f64, 3>; 3] = [f64::NAN, 2.0_f64, -0.0_f64].into_variables();
+        sort_by_value(&mut x);
+        assert_eq!(x.map(|x| x.dual().as_ref()[0]), [0.0_f64, 0.0_f64, 1.0_f64]);
+        assert_eq!(x.map(|x| x.dual().as_ref()[2]), [1.0_f64, 0.0_f64, 0.0_f64]);
+    }
+
+    #[test]
+    fn dot_empty() {
+        let empty: [DualNumber<f64, 2>; 0] = [];
+        assert_eq!(dot(&empty, &empty).value(), &0.0_f64);
+    }
+}
@@ -0,0 +1,194 @@
+//! Aligned text tables of derivatives for quick inspection during debugging
+//!
+//! Rows correspond to variables and columns to outputs,
+//! the formatter's precision applies to every derivative
+//!
+//!```
+//! use autodj::prelude::vector::*;
+//! use autodj::{jacobian, report::Report};
+//!
+//! let jac = jacobian(&|x: &[DualF64]| vec![x[0].mul_impl(&x[1]), x[1].clone()], &[2.0, 3.0]);
+//! let table = Report::jacobian(&jac).variables(["mass", "speed"]).outputs(["energy", "v"]);
+//! assert_eq!(
+//!     format!("{table:.1}"),
+//!     "       energy    v\n\
+//!      mass      3.0  0.0\n\
+//!      speed     2.0  1.0\n"
+//! );
+//! ```
+#![cfg(feature = "std")]
+
+use crate::{
+    eval::Jacobian,
+    fluid::{Dual, GradIter, Value},
+};
+use std::{
+    fmt::{Display, Formatter, Result},
+    prelude::v1::{format, String, ToString, Vec},
+};
+
+/// Table of derivatives of outputs (columns) with respect to variables (rows)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report<V: Value> {
+    /// names of rows
+    variables: Vec<String>,
+    /// names of columns
+    outputs: Vec<String>,
+    /// derivatives, a row per variable
+    entries: Vec<V>,
+}
+
+impl<V: Value> Report<V> {
+    /// Single column of a dense gradient, variables named `x0`, `x1`, …
+    #[must_use]
+    pub fn gradient(grad: &[V]) -> Self {
+        Self {
+            variables: (0..grad.len()).map(|index| format!("x{index}")).collect(),
+            outputs: Vec::from(["f".to_string()]),
+            entries: grad.to_vec(),
+        }
+    }
+
+    /// Transposed Jacobian, variables named `x0`, `x1`, … and outputs `f0`, `f1`, …
+    #[must_use]
+    pub fn jacobian(jac: &Jacobian<V>) -> Self {
+        let entries = (0..jac.columns())
+            .flat_map(|column| {
+                (0..jac.rows()).map(move |row| jac.get(row, column).unwrap_or_else(V::zero))
+            })
+            .collect();
+        Self {
+            variables: (0..jac.columns())
+                .map(|index| format!("x{index}"))
+                .collect(),
+            outputs: (0..jac.rows()).map(|index| format!("f{index}")).collect(),
+            entries,
+        }
+    }
+
+    /// Single column of a dual number, variables named by their keys,
+    /// prefixed with `x` for dense gradients like the alternate [`Display`]
+    #[must_use]
+    pub fn dual<D>(dual_number: &D) -> Self
+    where
+        D: Dual<Value = V>,
+        D::Grad: GradIter<V>,
+        <D::Grad as GradIter<V>>::Key: Display,
+    {
+        let grad = dual_number.dual();
+        let prefix = if grad.dim().is_some() { "x" } else { "" };
+        let (variables, entries) = grad
+            .derivatives()
+            .map(|(key, deriv)| (format!("{prefix}{key}"), deriv))
+            .unzip();
+        Self {
+            variables,
+            outputs: Vec::from(["f".to_string()]),
+            entries,
+        }
+    }
+
+    /// Rename variables in order, the rest keep their names
+    #[must_use]
+    pub fn variables<T: Display, I: IntoIterator<Item = T>>(mut self, names: I) -> Self {
+        for (slot, name) in self.variables.iter_mut().zip(names) {
+            *slot = name.to_string();
+        }
+        self
+    }
+
+    /// Rename outputs in order, the rest keep their names
+    #[must_use]
+    pub fn outputs<T: Display, I: IntoIterator<Item = T>>(mut self, names: I) -> Self {
+        for (slot, name) in self.outputs.iter_mut().zip(names) {
+            *slot = name.to_string();
+        }
+        self
+    }
+
+    /// Derivative of an output with respect to a variable. [`None`] out of range
+    #[must_use]
+    pub fn get(&self, variable: usize, output: usize) -> Option<V> {
+        (output < self.outputs.len())
+            .then(|| {
+                self.entries
+                    .get(variable * self.outputs.len() + output)
+                    .copied()
+            })
+            .flatten()
+    }
+}
+
+/// Names left-aligned, derivatives right-aligned, columns separated by two spaces
+impl<V: Value + Display> Display for Report<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let cells: Vec<String> = self
+            .entries
+            .iter()
+            .map(|deriv| match f.precision() {
+                Some(precision) => format!("{deriv:.precision$}"),
+                None => format!("{deriv}"),
+            })
+            .collect();
+        let columns = self.outputs.len();
+        let name_width = self
+            .variables
+            .iter()
+            .map(|name| name.chars().count())
+            .max()
+            .unwrap_or(0);
+        let widths: Vec<usize> = self
+            .outputs
+            .iter()
+            .enumerate()
+            .map(|(output, name)| {
+                cells
+                    .iter()
+                    .skip(output)
+                    .step_by(columns.max(1))
+                    .chain([name])
+                    .map(|cell| cell.chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        write!(f, "{:name_width$}", "")?;
+        for (name, width) in self.outputs.iter().zip(&widths) {
+            write!(f, "  {name:>width$}")?;
+        }
+        writeln!(f)?;
+        for (variable, row) in self.variables.iter().zip(cells.chunks(columns.max(1))) {
+            write!(f, "{variable:name_width$}")?;
+            for (cell, width) in row.iter().zip(&widths) {
+                write!(f, "  {cell:>width$}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "sparse")]
+    fn named_gradient() {
+        use crate::solid::sparse::named::{DualNumber, IntoVariable};
+
+        let pressure: DualNumber<f64> = 3.25_f64.into_variable_named("pressure".to_string());
+        let report = Report::dual(&pressure.mul_impl(&pressure));
+        assert_eq!(report.get(0, 0), Some(6.5_f64));
+        assert_eq!(format!("{report:.2}"), "             f\npressure  6.50\n");
+    }
+
+    #[test]
+    fn gradient_column() {
+        let report = Report::gradient(&[1.5_f64, -20.0_f64]).outputs(["loss"]);
+        assert_eq!(report.get(1, 0), Some(-20.0_f64));
+        assert_eq!(report.get(0, 1), None);
+        assert_eq!(report.to_string(), "    loss\nx0   1.5\nx1   -20\n");
+    }
+}
@@ -0,0 +1,195 @@
+//! [`Scaling`] for non-dimensionalization of residual systems
+//!
+//! Variables and equations of engineering models often differ by many orders of magnitude,
+//! which slows down or breaks Newton-like iterations.
+//! [`Scaling`] keeps characteristic magnitudes of both
+//! and converts values and gradients between dimensional and dimensionless spaces.
+//!
+//!```
+//! use autodj::prelude::vector::*;
+//! use autodj::scaling::Scaling;
+//!
+//! let scaling = Scaling::new([1e5, 1e-3], [1e2]);
+//!
+//! // dimensional residual: r(p, v) = p * v - 100
+//! let residual = |x: &[DualF64]| vec![x[0].mul_impl(&x[1]) - 100.0];
+//! let scaled = scaling.wrap(&residual);
+//!
+//! let x_hat = scaling.nondimensionalize_variables(&[2e5, 1e-3]);
+//! assert_eq!(x_hat, [2.0, 1.0]);
+//!
+//! let r_hat = scaled(&x_hat.into_variables());
+//! assert_eq!(r_hat[0].value(), &1.0);
+//! assert_eq!(r_hat[0].dual().as_ref(), &[1.0, 2.0]);
+//! ```
+#![cfg(feature = "alloc")]
+
+use crate::{
+    fluid::{Dual, Value},
+    function::VectorDualFn,
+};
+use std::prelude::v1::Vec;
+
+/// Per-variable and per-equation scale factors
+///
+/// Variables and equations without a corresponding scale factor are left unscaled
+#[derive(Clone, Debug, PartialEq, PartialOrd, Default)]
+pub struct Scaling<V: Value> {
+    /// characteristic magnitudes of variables
+    variables: Vec<V>,
+    /// characteristic magnitudes of equations (residuals)
+    equations: Vec<V>,
+}
+
+impl<V: Value> Scaling<V> {
+    /// Construct from characteristic magnitudes of variables and equations
+    pub fn new<Variables, Equations>(variables: Variables, equations: Equations) -> Self
+    where
+        Variables: Into<Vec<V>>,
+        Equations: Into<Vec<V>>,
+    {
+        Self {
+            variables: variables.into(),
+            equations: equations.into(),
+        }
+    }
+
+    /// Scale factors of variables
+    #[must_use]
+    pub fn variables(&self) -> &[V] {
+        &self.variables
+    }
+
+    /// Scale factors of equations
+    #[must_use]
+    pub fn equations(&self) -> &[V] {
+        &self.equations
+    }
+
+    /// Scale factor of a variable by its index
+    fn variable(&self, index: usize) -> V {
+        self.variables.get(index).copied().unwrap_or_else(V::one)
+    }
+
+    /// Scale factor of an equation by its index
+    fn equation(&self, index: usize) -> V {
+        self.equations.get(index).copied().unwrap_or_else(V::one)
+    }
+
+    /// Dimensional variables `x` into dimensionless `x / scale`
+    pub fn nondimensionalize_variables(&self, variables: &[V]) -> Vec<V> {
+        (0..variables.len())
+            .zip(variables)
+            .map(|(index, &value)| value / self.variable(index))
+            .collect()
+    }
+
+    /// Dimensionless variables (or Newton steps) `x` into dimensional `x * scale`
+    pub fn dimensionalize_variables(&self, variables: &[V]) -> Vec<V> {
+        (0..variables.len())
+            .zip(variables)
+            .map(|(index, &value)| value * self.variable(index))
+            .collect()
+    }
+
+    /// Dimensional residuals `r` into dimensionless `r / scale`
+    pub fn nondimensionalize_residuals(&self, residuals: &[V]) -> Vec<V> {
+        (0..residuals.len())
+            .zip(residuals)
+            .map(|(index, &value)| value / self.equation(index))
+            .collect()
+    }
+
+    /// Dimensionless residuals `r` into dimensional `r * scale`
+    pub fn dimensionalize_residuals(&self, residuals: &[V]) -> Vec<V> {
+        (0..residuals.len())
+            .zip(residuals)
+            .map(|(index, &value)| value * self.equation(index))
+            .collect()
+    }
+
+    /// Dimensionless gradient of an equation w.r.t. dimensionless variables
+    /// into the dimensional one
+    pub fn dimensionalize_gradient(&self, equation: usize, gradient: &[V]) -> Vec<V> {
+        let equation_scale = self.equation(equation);
+        (0..gradient.len())
+            .zip(gradient)
+            .map(|(index, &deriv)| deriv * equation_scale / self.variable(index))
+            .collect()
+    }
+
+    /// Wrap a dimensional residual function into a dimensionless one.
+    ///
+    /// The wrapped function takes dimensionless variables
+    /// and returns dimensionless residuals,
+    /// so their gradients are w.r.t. dimensionless variables as well
+    pub fn wrap<'scaling, D, F>(
+        &'scaling self,
+        residual: &'scaling F,
+    ) -> impl Fn(&[D]) -> Vec<D> + 'scaling
+    where
+        D: Dual<Value = V>,
+        F: VectorDualFn<D> + ?Sized,
+    {
+        move |variables: &[D]| {
+            let dimensional = (0..variables.len())
+                .zip(variables)
+                .map(|(index, variable)| {
+                    let scale = self.variable(index);
+                    variable.chain(|&value| (value * scale, scale))
+                })
+                .collect::<Vec<D>>();
+            residual
+                .eval(&dimensional)
+                .into_iter()
+                .enumerate()
+                .map(|(index, equation)| {
+                    let scale = self.equation(index).recip();
+                    equation.chain(|&value| (value * scale, scale))
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let scaling = Scaling::new([2.0_f64, 4.0_f64], [8.0_f64]);
+        let x = [1.0_f64, 1.0_f64, 1.0_f64];
+        let x_hat = scaling.nondimensionalize_variables(&x);
+        assert_eq!(x_hat, [0.5_f64, 0.25_f64, 1.0_f64]);
+        assert_eq!(scaling.dimensionalize_variables(&x_hat), x);
+        let r_hat = scaling.nondimensionalize_residuals(&[16.0_f64]);
+        assert_eq!(scaling.dimensionalize_residuals(&r_hat), [16.0_f64]);
+    }
+
+    #[test]
+    fn gradient() {
+        use crate::solid::array::{DualNumber, IntoVariables};
+
+        let scaling = Scaling::new([2.0_f64, 4.0_f64], [8.0_f64]);
+        let residual = |x: &[DualNumber<f64, 2>]| {
+            Vec::from([x
+                .iter()
+                .fold(DualNumber::parameter(0.0_f64), |acc, &x| acc + x)])
+        };
+        let scaled = scaling.wrap(&residual);
+        let x_hat = [1.0_f64, 1.0_f64].into_variables();
+        let residuals = scaled(&x_hat);
+        let [equation] = residuals.as_slice() else {
+            panic!("There should be exactly one equation");
+        };
+        let grad = *equation.dual().as_ref();
+        assert_eq!(equation.value(), &0.75_f64);
+        assert_eq!(grad, [0.25_f64, 0.5_f64]);
+        assert_eq!(
+            scaling.dimensionalize_gradient(0, &grad),
+            [1.0_f64, 1.0_f64]
+        );
+    }
+}
@@ -0,0 +1,92 @@
+//! Smooth approximations of non-differentiable functions
+//!
+//! Each function takes a smoothing parameter `epsilon`:
+//! the approximation error is of its order,
+//! and the original function is restored as `epsilon → 0`.
+//! All the approximations are built upon `√(x² + ε²)`,
+//! so they are consistent with each other.
+//!
+//!```
+//! use autodj::prelude::single::*;
+//! use autodj::smooth::{smooth_abs, smooth_max};
+//!
+//! let x: DualF64 = 0.0.into_variable();
+//! assert_eq!(smooth_abs(&x, 1e-3).decompose(), (1e-3, 0.0));
+//!
+//! let y: DualF64 = 1.0.into();
+//! let (max, dmax) = smooth_max(&x, &y, 1e-3).decompose();
+//! assert!((max - 1.0).abs() < 1e-6);
+//! assert!(dmax.abs() < 1e-6);
+//! ```
+
+use crate::fluid::{half, Dual};
+use num_traits::{real::Real, One};
+
+/// Multiply a dual number by `1/2`
+fn halve<D: Dual>(x: &D) -> D {
+    x.chain(|&x| (x * half::<D::Value>(), half::<D::Value>()))
+}
+
+/// Smooth approximation of [`Dual::abs`]: `√(x² + ε²)`
+#[must_use]
+pub fn smooth_abs<D: Dual>(x: &D, epsilon: D::Value) -> D {
+    x.chain(|&x| {
+        let value = (x * x + epsilon * epsilon).sqrt();
+        (value, x / value)
+    })
+}
+
+/// Smooth approximation of Heaviside step function: `(1 + x/√(x² + ε²))/2`
+#[must_use]
+pub fn smooth_heaviside<D: Dual>(x: &D, epsilon: D::Value) -> D {
+    x.chain(|&x| {
+        let squared = epsilon * epsilon;
+        let root = (x * x + squared).sqrt();
+        let value = (D::Value::one() + x / root) * half::<D::Value>();
+        (value, squared * half::<D::Value>() / (root * root * root))
+    })
+}
+
+/// Smooth approximation of maximum: `(a + b + √((a - b)² + ε²))/2`
+#[must_use]
+pub fn smooth_max<D: Dual>(a: &D, b: &D, epsilon: D::Value) -> D {
+    let sum = a.add_impl(b);
+    halve(&sum.add_impl(&smooth_abs(&a.sub_impl(b), epsilon)))
+}
+
+/// Smooth approximation of minimum: `(a + b - √((a - b)² + ε²))/2`
+#[must_use]
+pub fn smooth_min<D: Dual>(a: &D, b: &D, epsilon: D::Value) -> D {
+    let sum = a.add_impl(b);
+    halve(&sum.sub_impl(&smooth_abs(&a.sub_impl(b), epsilon)))
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+    use crate::solid::array::{DualNumber, IntoVariables};
+
+    #[test]
+    fn min_max_partials() {
+        let [a, b]: [DualNumber<f64, 2>; 2] = [3.0_f64, 1.0_f64].into_variables();
+        let epsilon = 1e-8_f64;
+        let max = smooth_max(&a, &b, epsilon);
+        let min = smooth_min(&a, &b, epsilon);
+        assert!((max.value() - 3.0_f64).abs() < epsilon);
+        assert!((min.value() - 1.0_f64).abs() < epsilon);
+        assert_eq!(max.dual().as_ref(), &[1.0_f64, 0.0_f64]);
+        assert_eq!(min.dual().as_ref(), &[0.0_f64, 1.0_f64]);
+    }
+
+    #[test]
+    fn heaviside_is_abs_derivative() {
+        use crate::solid::single::IntoVariable;
+
+        let x = 0.25_f64.into_variable();
+        let epsilon = 0.5_f64;
+        let (_, dabs) = smooth_abs(&x, epsilon).decompose();
+        let (step, _) = smooth_heaviside(&x, epsilon).decompose();
+        assert!((step - (1.0_f64 + dabs) * 0.5_f64).abs() < f64::EPSILON);
+    }
+}
@@ -3,10 +3,11 @@
 use std::{
     borrow::BorrowMut,
     fmt::{Display, LowerExp},
+    iter::{Product, Sum},
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use crate::fluid::{display_impl, lower_exp_impl, Dual, Grad, Value};
+use crate::fluid::{check_finite, display_impl, lower_exp_impl, Dual, Grad, GradIter, Value};
 
 /// Default generic [`Dual`] implementor: a struct with two fields
 #[derive(Clone, Debug, PartialEq, PartialOrd, Default, Hash)]
@@ -28,6 +29,40 @@ where
 {
     type Output = Self;
 
+    fn neg(self) -> Self::Output {
+        self.neg_owned()
+    }
+}
+
+/// Compares only the value, ignoring derivatives
+impl<N, D> PartialEq<N> for DualNumber<N, D>
+where
+    N: Value,
+    D: Grad<N>,
+{
+    fn eq(&self, other: &N) -> bool {
+        self.value.eq(other)
+    }
+}
+
+/// Compares only the value, ignoring derivatives
+impl<N, D> PartialOrd<N> for DualNumber<N, D>
+where
+    N: Value,
+    D: Grad<N>,
+{
+    fn partial_cmp(&self, other: &N) -> Option<std::cmp::Ordering> {
+        self.value.partial_cmp(other)
+    }
+}
+
+impl<N, D> Neg for &DualNumber<N, D>
+where
+    N: Value,
+    D: Grad<N>,
+{
+    type Output = DualNumber<N, D>;
+
     fn neg(self) -> Self::Output {
         self.neg_impl()
     }
@@ -49,7 +84,7 @@ where
     D: Grad<N>,
 {
     fn mul_assign(&mut self, rhs: Self) {
-        let _unused = self.mul_assign_impl(&rhs);
+        let _unused = self.mul_assign_owned(rhs);
     }
 }
 
@@ -69,7 +104,7 @@ where
     D: Grad<N>,
 {
     fn add_assign(&mut self, rhs: Self) {
-        let _unused = self.add_assign_impl(&rhs);
+        let _unused = self.add_assign_owned(rhs);
     }
 }
 
@@ -79,8 +114,9 @@ where
     D: Grad<N>,
 {
     type Output = Self;
-    fn div(self, rhs: Self) -> Self::Output {
-        self.div_impl(&rhs)
+    fn div(mut self, rhs: Self) -> Self::Output {
+        let _unused = self.div_assign_impl(&rhs);
+        self
     }
 }
 
@@ -90,8 +126,9 @@ where
     D: Grad<N>,
 {
     type Output = Self;
-    fn sub(self, rhs: Self) -> Self::Output {
-        self.sub_impl(&rhs)
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        let _unused = self.sub_assign_impl(&rhs);
+        self
     }
 }
 
@@ -101,8 +138,9 @@ where
     D: Grad<N>,
 {
     type Output = Self;
-    fn mul(self, rhs: Self) -> Self::Output {
-        self.mul_impl(&rhs)
+    fn mul(mut self, rhs: Self) -> Self::Output {
+        let _unused = self.mul_assign_owned(rhs);
+        self
     }
 }
 
@@ -113,11 +151,216 @@ where
 {
     type Output = Self;
 
-    fn add(self, rhs: Self) -> Self::Output {
-        self.add_impl(&rhs)
+    fn add(mut self, rhs: Self) -> Self::Output {
+        let _unused = self.add_assign_owned(rhs);
+        self
     }
 }
 
+impl<N, D> AddAssign<N> for DualNumber<N, D>
+where
+    N: Value,
+    D: Grad<N>,
+{
+    fn add_assign(&mut self, rhs: N) {
+        #[cfg(feature = "stats")]
+        crate::stats::record_operation();
+        #[cfg(all(feature = "nan-check", debug_assertions))]
+        let lhs = self.value;
+        self.value += rhs;
+        check_finite!(self, "addition", lhs, rhs);
+    }
+}
+
+impl<N, D> SubAssign<N> for DualNumber<N, D>
+where
+    N: Value,
+    D: Grad<N>,
+{
+    fn sub_assign(&mut self, rhs: N) {
+        #[cfg(feature = "stats")]
+        crate::stats::record_operation();
+        #[cfg(all(feature = "nan-check", debug_assertions))]
+        let lhs = self.value;
+        self.value -= rhs;
+        check_finite!(self, "subtraction", lhs, rhs);
+    }
+}
+
+impl<N, D> MulAssign<N> for DualNumber<N, D>
+where
+    N: Value,
+    D: Grad<N>,
+{
+    fn mul_assign(&mut self, rhs: N) {
+        #[cfg(feature = "stats")]
+        crate::stats::record_operation();
+        #[cfg(all(feature = "nan-check", debug_assertions))]
+        let lhs = self.value;
+        self.value *= rhs;
+        self.dual *= rhs;
+        check_finite!(self, "scaling", lhs, rhs);
+    }
+}
+
+impl<N, D> DivAssign<N> for DualNumber<N, D>
+where
+    N: Value,
+    D: Grad<N>,
+{
+    #[allow(
+        clippy::suspicious_op_assign_impl,
+        reason = "division by a constant scales by its reciprocal"
+    )]
+    fn div_assign(&mut self, rhs: N) {
+        *self *= rhs.recip();
+    }
+}
+
+impl<N, D> Add<N> for DualNumber<N, D>
+where
+    N: Value,
+    D: Grad<N>,
+{
+    type Output = Self;
+
+    fn add(mut self, rhs: N) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<N, D> Sub<N> for DualNumber<N, D>
+where
+    N: Value,
+    D: Grad<N>,
+{
+    type Output = Self;
+
+    fn sub(mut self, rhs: N) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<N, D> Mul<N> for DualNumber<N, D>
+where
+    N: Value,
+    D: Grad<N>,
+{
+    type Output = Self;
+
+    fn mul(mut self, rhs: N) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+
+impl<N, D> Div<N> for DualNumber<N, D>
+where
+    N: Value,
+    D: Grad<N>,
+{
+    type Output = Self;
+
+    fn div(mut self, rhs: N) -> Self::Output {
+        self /= rhs;
+        self
+    }
+}
+
+impl<N, D> Sum for DualNumber<N, D>
+where
+    N: Value,
+    D: Grad<N>,
+{
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::parameter(N::zero()), |mut acc, item| {
+            acc += item;
+            acc
+        })
+    }
+}
+
+impl<'a, N, D> Sum<&'a Self> for DualNumber<N, D>
+where
+    N: Value,
+    D: Grad<N>,
+{
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::parameter(N::zero()), |mut acc, item| {
+            let _unused = acc.add_assign_impl(item);
+            acc
+        })
+    }
+}
+
+impl<N, D> Product for DualNumber<N, D>
+where
+    N: Value,
+    D: Grad<N>,
+{
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::parameter(N::one()), |mut acc, item| {
+            acc *= item;
+            acc
+        })
+    }
+}
+
+impl<'a, N, D> Product<&'a Self> for DualNumber<N, D>
+where
+    N: Value,
+    D: Grad<N>,
+{
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::parameter(N::one()), |mut acc, item| {
+            let _unused = acc.mul_assign_impl(item);
+            acc
+        })
+    }
+}
+
+/// Arithmetic with a primitive float on the left-hand side.
+/// The orphan rule forbids doing so for generic [`Value`]
+macro_rules! impl_scalar_lhs {
+    ($($scalar:ty),*) => {$(
+        impl<D: Grad<$scalar>> Add<DualNumber<$scalar, D>> for $scalar {
+            type Output = DualNumber<$scalar, D>;
+
+            fn add(self, rhs: DualNumber<$scalar, D>) -> Self::Output {
+                rhs + self
+            }
+        }
+
+        impl<D: Grad<$scalar>> Sub<DualNumber<$scalar, D>> for $scalar {
+            type Output = DualNumber<$scalar, D>;
+
+            fn sub(self, rhs: DualNumber<$scalar, D>) -> Self::Output {
+                -rhs + self
+            }
+        }
+
+        impl<D: Grad<$scalar>> Mul<DualNumber<$scalar, D>> for $scalar {
+            type Output = DualNumber<$scalar, D>;
+
+            fn mul(self, rhs: DualNumber<$scalar, D>) -> Self::Output {
+                rhs * self
+            }
+        }
+
+        impl<D: Grad<$scalar>> Div<DualNumber<$scalar, D>> for $scalar {
+            type Output = DualNumber<$scalar, D>;
+
+            fn div(self, rhs: DualNumber<$scalar, D>) -> Self::Output {
+                DualNumber::parameter(self) / rhs
+            }
+        }
+    )*};
+}
+
+impl_scalar_lhs!(f32, f64);
+
 impl<N, D> Dual for DualNumber<N, D>
 where
     N: Value,
@@ -152,13 +395,40 @@ where
     }
 }
 
+impl<N: Value, D: Grad<N>> DualNumber<N, D> {
+    /// Same as [`Dual::new`] but usable in constant expressions, e.g. for `static` model constants
+    #[must_use]
+    pub const fn new(value: N, dual: D) -> Self {
+        Self { value, dual }
+    }
+}
+
 impl<V: Value, G: Grad<V>> From<V> for DualNumber<V, G> {
     fn from(value: V) -> Self {
         Self::parameter(value)
     }
 }
 
-impl<V: Value + Display, G: Grad<V> + Display> Display for DualNumber<V, G> {
+/// Width and precision apply to the value and every derivative, as for ordinary floats.
+/// The alternate form `{:#}` labels derivatives in order of [`GradIter::derivatives`]
+///```
+/// use autodj::prelude::array::*;
+///
+/// let [x, y] = [1.0, 2.0].into_variables();
+/// let f = x * y / 3.0;
+/// assert_eq!(format!("{f:.2}"), "0.67+[0.67, 0.33]∆");
+/// assert_eq!(format!("{f:5.1}"), "  0.7+[  0.7,   0.3]∆");
+/// assert_eq!(format!("{f:#.2}"), "f = 0.67, ∂f/∂x0 = 0.67, ∂f/∂x1 = 0.33");
+///
+/// let single = autodj::solid::single::DualF64::new(2.0 / 3.0, 1.0);
+/// assert_eq!(format!("{single:.3}"), "0.667+1.000∆");
+/// ```
+impl<V, G> Display for DualNumber<V, G>
+where
+    V: Value + Display,
+    G: Grad<V> + GradIter<V> + Display,
+    G::Key: Display,
+{
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         display_impl(self, f)
     }
@@ -170,6 +440,16 @@ impl<V: Value + LowerExp, G: Grad<V> + LowerExp> LowerExp for DualNumber<V, G> {
     }
 }
 
+/// Error of parsing a dual number from its [`Display`] format
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ParseDualError;
+
+impl Display for ParseDualError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid dual number literal")
+    }
+}
+
 impl<N, D> Copy for DualNumber<N, D>
 where
     N: Value + Copy,
@@ -178,6 +458,11 @@ where
 }
 
 pub mod array;
+pub mod fixed;
+pub mod heapless;
+pub mod shared;
+pub mod simd;
 pub mod single;
+pub mod smallvec;
 pub mod sparse;
 pub mod vector;
@@ -1,11 +1,17 @@
-//! [`crate::array::DualNumber`] for a specific number of variables
+//! [`crate::solid::array::DualNumber`] for a specific number of variables
 
-use crate::fluid::{Dual, Value};
-use num_traits::Zero;
+use crate::{
+    fluid::{write_flagged, Dual, Value},
+    solid::ParseDualError,
+};
+use num_traits::{ConstZero, Zero};
+#[cfg(feature = "alloc")]
+use std::prelude::v1::Vec;
 use std::{
     array::from_fn,
     fmt::{Display, LowerExp},
-    ops::{Add, AddAssign, Mul, MulAssign, Neg},
+    ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg},
+    str::FromStr,
 };
 
 /// Array of dual components
@@ -18,12 +24,98 @@ impl<V: Value, const N: usize> AsRef<[V; N]> for Grad<V, N> {
     }
 }
 
+impl<V: Value, const N: usize> Index<usize> for Grad<V, N> {
+    type Output = V;
+
+    #[allow(
+        clippy::indexing_slicing,
+        reason = "out-of-bounds indexing panics by contract"
+    )]
+    fn index(&self, index: usize) -> &V {
+        &self.0[index]
+    }
+}
+
+impl<V: Value, const N: usize> IndexMut<usize> for Grad<V, N> {
+    #[allow(
+        clippy::indexing_slicing,
+        reason = "out-of-bounds indexing panics by contract"
+    )]
+    fn index_mut(&mut self, index: usize) -> &mut V {
+        &mut self.0[index]
+    }
+}
+
 impl<V: Value, const N: usize, Arr: Into<[V; N]>> From<Arr> for Grad<V, N> {
     fn from(value: Arr) -> Self {
         Self(value.into())
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<V: Value, const N: usize> From<Grad<V, N>> for Vec<V> {
+    fn from(value: Grad<V, N>) -> Self {
+        value.0.into()
+    }
+}
+
+impl<V: Value, const N: usize> crate::fluid::GradIter<V> for Grad<V, N> {
+    type Key = usize;
+
+    fn derivatives(&self) -> impl Iterator<Item = (usize, V)> + '_ {
+        self.0.iter().copied().enumerate()
+    }
+
+    fn dim(&self) -> Option<usize> {
+        Some(N)
+    }
+}
+
+impl<V: Value, const N: usize> Grad<V, N> {
+    /// Gradient of given derivatives, usable in constant expressions
+    #[must_use]
+    pub const fn new(derivatives: [V; N]) -> Self {
+        Self(derivatives)
+    }
+}
+
+impl<V: Value, const N: usize> Grad<V, N> {
+    /// Sparse gradient with derivatives paired with given keys. Zero derivatives are omitted
+    ///```
+    /// use autodj::solid::array::Grad;
+    ///
+    /// let grad: Grad<f64, 2> = [0.0, 2.0].into();
+    /// let sparse = grad.to_sparse(&['x', 'y']);
+    /// assert_eq!(sparse.as_ref().get(&'y'), Some(&2.0));
+    /// assert_eq!(sparse.as_ref().get(&'x'), None);
+    /// ```
+    #[cfg(feature = "sparse")]
+    #[must_use]
+    pub fn to_sparse<Key: crate::solid::sparse::GradKey>(
+        &self,
+        keys: &[Key; N],
+    ) -> crate::solid::sparse::Grad<Key, V> {
+        crate::solid::sparse::from_dense(&self.0, keys)
+    }
+}
+
+impl<V: Value, const N: usize> Grad<V, N> {
+    /// Place derivatives into a gradient over `M` variables starting from position `OFFSET`.
+    /// Other derivatives are zero.
+    /// Fails to compile unless `OFFSET + N ≤ M`
+    #[must_use]
+    pub fn embed<const M: usize, const OFFSET: usize>(self) -> Grad<V, M> {
+        const { assert!(OFFSET + N <= M, "embedded gradient must fit") };
+        Grad(from_fn(|index| {
+            index
+                .checked_sub(OFFSET)
+                .and_then(|index| self.0.get(index))
+                .copied()
+                .unwrap_or_else(V::zero)
+        }))
+    }
+}
+
 impl<V: Value, const N: usize> AddAssign for Grad<V, N> {
     fn add_assign(&mut self, rhs: Self) {
         for (index, elem) in self.0.iter_mut().enumerate() {
@@ -40,6 +132,14 @@ impl<V: Value, const N: usize> AddAssign for Grad<V, N> {
     }
 }
 
+impl<V: Value, const N: usize> crate::fluid::Grad<V> for Grad<V, N> {
+    fn axpy(&mut self, alpha: V, x: &Self) {
+        for (to, &from) in self.0.iter_mut().zip(&x.0) {
+            *to += alpha * from;
+        }
+    }
+}
+
 impl<V: Value, const N: usize> MulAssign<V> for Grad<V, N> {
     fn mul_assign(&mut self, rhs: V) {
         for elem in &mut self.0 {
@@ -79,6 +179,10 @@ impl<V: Value, const N: usize> Neg for Grad<V, N> {
     }
 }
 
+impl<V: Value + ConstZero, const N: usize> ConstZero for Grad<V, N> {
+    const ZERO: Self = Self([V::ZERO; N]);
+}
+
 impl<V, const N: usize> Zero for Grad<V, N>
 where
     V: Value,
@@ -93,6 +197,12 @@ where
     }
 }
 
+impl<V: Value, const N: usize> Default for Grad<V, N> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
 /// For statically-known number of variables
 ///```
 /// use autodj::prelude::array::*;
@@ -104,6 +214,85 @@ where
 /// ```
 pub type DualNumber<V, const N: usize> = crate::solid::DualNumber<V, Grad<V, N>>;
 
+/// Specialization for [`f64`]
+pub type DualF64<const N: usize> = DualNumber<f64, N>;
+
+/// Specialization for [`f32`]
+pub type DualF32<const N: usize> = DualNumber<f32, N>;
+
+/// Parses the [`Display`] format back
+///```
+/// use autodj::prelude::array::*;
+///
+/// let [x, y] = [1.5, -2.0].into_variables();
+/// let f = x * y;
+/// let parsed: DualF64<2> = f.to_string().parse().unwrap();
+/// assert_eq!(parsed, f);
+/// assert!("1+[1.0]∆".parse::<DualF64<2>>().is_err());
+/// ```
+impl<V: Value + FromStr, const N: usize> FromStr for DualNumber<V, N> {
+    type Err = ParseDualError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (value, derivs) = s
+            .strip_suffix("]∆")
+            .and_then(|body| body.split_once("+["))
+            .ok_or(ParseDualError)?;
+        let mut derivs = derivs
+            .split(", ")
+            .filter(|deriv| !deriv.is_empty())
+            .map(str::parse);
+        let mut grad = [V::zero(); N];
+        for slot in &mut grad {
+            *slot = derivs
+                .next()
+                .ok_or(ParseDualError)?
+                .map_err(|_| ParseDualError)?;
+        }
+        if derivs.next().is_some() {
+            return Err(ParseDualError);
+        }
+        Ok(Self::new(
+            value.parse().map_err(|_| ParseDualError)?,
+            Grad(grad),
+        ))
+    }
+}
+
+impl<V: Value> From<crate::solid::single::DualNumber<V>> for DualNumber<V, 1> {
+    fn from(value: crate::solid::single::DualNumber<V>) -> Self {
+        let (value, deriv) = value.decompose();
+        Self::new(value, Grad([deriv]))
+    }
+}
+
+impl<V: Value, const N: usize> DualNumber<V, N> {
+    /// Combine with duals over a different set of variables
+    /// by re-indexing the `N` variables of `self` as `OFFSET..OFFSET + N` among `M`.
+    /// Fails to compile unless `OFFSET + N ≤ M`
+    ///```
+    /// use autodj::prelude::array::*;
+    ///
+    /// let [x, y] = [2.0, 3.0].into_variables();
+    /// let [z] = [4.0].into_variables();
+    /// let f: DualNumber<f64, 3> = (x * y).embed::<3, 0>() + z.embed::<3, 2>();
+    /// assert_eq!(f.dual().as_ref(), &[3.0, 2.0, 1.0]);
+    /// ```
+    ///
+    /// Gradients which do not fit are rejected at compile time
+    ///```compile_fail
+    /// use autodj::prelude::array::*;
+    ///
+    /// let [x, y] = [2.0, 3.0].into_variables();
+    /// let _ = (x * y).embed::<2, 1>();
+    /// ```
+    #[must_use]
+    pub fn embed<const M: usize, const OFFSET: usize>(self) -> DualNumber<V, M> {
+        let (value, grad) = self.decompose();
+        DualNumber::new(value, grad.embed::<M, OFFSET>())
+    }
+}
+
 /// Construct independent variables from array
 pub trait IntoVariables<V: Value, const N: usize>: Into<[V; N]> {
     /// Construct independent variables from array
@@ -125,29 +314,164 @@ pub trait IntoVariables<V: Value, const N: usize>: Into<[V; N]> {
             )
         })
     }
+
+    /// Construct dual variables with given seeds as gradients instead of the identity matrix
+    ///```
+    /// use autodj::prelude::array::*;
+    ///
+    /// // derivative along direction [1, 2]
+    /// let [x, y] = [3.0, 4.0].into_variables_with_seeds([[1.0], [2.0]]);
+    /// let f = x * x + y;
+    /// assert_eq!(f.dual().as_ref(), &[8.0]);
+    /// ```
+    fn into_variables_with_seeds<const M: usize>(
+        self,
+        seeds: [[V; M]; N],
+    ) -> [DualNumber<V, M>; N] {
+        let arr: [V; N] = self.into();
+        let mut values = arr.into_iter();
+        seeds.map(|seed| {
+            DualNumber::new(
+                values
+                    .next()
+                    .unwrap_or_else(|| panic!("There should be a value for each seed")),
+                Grad(seed),
+            )
+        })
+    }
 }
 impl<V: Value, const N: usize, IntoArray> IntoVariables<V, N> for IntoArray where Self: Into<[V; N]> {}
 
+/// Independent variables together with parameters of the same gradient arity,
+/// for functions of `NV` variables and `NP` parameters
+///```
+/// use autodj::prelude::array::*;
+///
+/// let ([x, y], [a, b]) = variables_and_parameters([2.0, 3.0], [10.0, 100.0]);
+/// let f = a * x + b * y;
+/// assert_eq!(f.value(), &320.0);
+/// assert_eq!(f.dual().as_ref(), &[10.0, 100.0]);
+/// ```
+pub fn variables_and_parameters<V: Value, const NV: usize, const NP: usize>(
+    variables: [V; NV],
+    parameters: [V; NP],
+) -> ([DualNumber<V, NV>; NV], [DualNumber<V, NV>; NP]) {
+    (
+        variables.into_variables(),
+        parameters.map(DualNumber::parameter),
+    )
+}
+
+/// Same as [`Dual::parameter`] but usable in constant expressions,
+/// e.g. for `static` model constants and lookup tables
+///```
+/// use autodj::prelude::array::*;
+/// use autodj::solid::array::{self, Grad};
+///
+/// static GRAVITY: DualF64<2> = array::parameter(9.81);
+/// const TABLE: [DualF64<2>; 2] = [
+///     DualF64::new(1.0, Grad::new([1.0, 0.0])),
+///     DualF64::new(2.0, Grad::new([0.0, 1.0])),
+/// ];
+///
+/// let [length, _] = [2.0, 0.5].into_variables();
+/// assert_eq!((length / GRAVITY).dual().as_ref(), &[1.0 / 9.81, 0.0]);
+/// assert_eq!((TABLE[0] * TABLE[1]).dual().as_ref(), &[2.0, 1.0]);
+/// ```
+#[must_use]
+pub const fn parameter<V: Value + ConstZero, const N: usize>(value: V) -> DualNumber<V, N> {
+    DualNumber::new(value, Grad::ZERO)
+}
+
+/// Bind independent [`DualNumber`]s to a tuple, with `N` counted at compile time
+///```
+/// use autodj::prelude::array::*;
+/// use autodj::variables;
+///
+/// let (x, y, z) = variables![2.0, 3.0, 5.0];
+/// let f = x * y + z;
+/// assert_eq!(f.value(), &11.0);
+/// assert_eq!(f.dual().as_ref(), &[3.0, 2.0, 1.0]);
+/// ```
+#[macro_export]
+macro_rules! variables {
+    // every recursion level introduces a distinct hygienic `variable` binding
+    (@bind [$($bindings:ident)*] $array:ident; $head:expr $(, $tail:expr)*) => {
+        $crate::variables!(@bind [$($bindings)* variable] $array; $($tail),*)
+    };
+    (@bind [$($bindings:ident)*] $array:ident;) => {{
+        let [$($bindings),*] = $array;
+        ($($bindings,)*)
+    }};
+    ($($value:expr),+ $(,)?) => {{
+        let array = $crate::solid::array::IntoVariables::into_variables([$($value),+]);
+        $crate::variables!(@bind [] array; $($value),+)
+    }};
+}
+
+/// Set of independent variables whose values can be updated in place
+///```
+/// use autodj::prelude::array::*;
+///
+/// let mut variables: DualVariables<f64, 2> = [1.0, 2.0].into();
+/// variables.set_values(&[3.0, 4.0]);
+/// let [x, y] = variables.into_inner();
+/// assert_eq!(x.value(), &3.0);
+/// assert_eq!(y.dual().as_ref(), &[0.0, 1.0]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DualVariables<V: Value, const N: usize>([DualNumber<V, N>; N]);
+
+impl<V: Value, const N: usize, Arr: Into<[V; N]>> From<Arr> for DualVariables<V, N> {
+    fn from(value: Arr) -> Self {
+        Self(value.into().into_variables())
+    }
+}
+
+impl<V: Value, const N: usize> AsRef<[DualNumber<V, N>; N]> for DualVariables<V, N> {
+    fn as_ref(&self) -> &[DualNumber<V, N>; N] {
+        &self.0
+    }
+}
+
+impl<V: Value, const N: usize> DualVariables<V, N> {
+    /// Overwrite values of the variables keeping their gradients, e.g. between Newton iterations
+    pub fn set_values(&mut self, values: &[V; N]) {
+        for (variable, &value) in self.0.iter_mut().zip(values) {
+            *variable.value_mut() = value;
+        }
+    }
+
+    /// Take the variables out of the set
+    #[must_use]
+    pub fn into_inner(self) -> [DualNumber<V, N>; N] {
+        self.0
+    }
+}
+
+/// Width and precision apply to every derivative
 impl<V: Value, const N: usize> Display for Grad<V, N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "+{:?}", self.0)
+        write!(f, "+[")?;
+        for (index, deriv) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write_flagged!(f, deriv, "", "?")?;
+        }
+        write!(f, "]")
     }
 }
 
+/// Width and precision apply to every derivative
 impl<V: Value + LowerExp, const N: usize> LowerExp for Grad<V, N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "+[")?;
-        for index in 1..=N {
-            let deriv_value = self
-                .0
-                .get(index - 1)
-                // TODO: consider using `unsafe get_unchecked()` or relax clippy lints
-                .unwrap_or_else(|| panic!("The index requested here should always be valid"));
-            write!(f, "{deriv_value:e}")?;
-            if index == N {
-                break;
+        for (index, deriv) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
             }
-            write!(f, ", ")?;
+            write_flagged!(f, deriv, "", "e")?;
         }
         write!(f, "]")
     }
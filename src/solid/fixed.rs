@@ -0,0 +1,299 @@
+//! [`crate::solid::fixed::DualNumber`] with sparse gradient stored inline with a fixed capacity
+//!
+//! No heap allocations are performed, so it is suitable for `no_std` targets without an allocator.
+//! Keys are looked up by linear probing over the slots, which is fast for small capacities.
+//!
+//! # Overflow policy
+//!
+//! Arithmetic operations which would need more than `CAP` distinct keys **panic**.
+//! Use [`Grad::try_insert`] and [`Grad::try_add_assign`] to handle overflow explicitly.
+//!
+//!```
+//! use autodj::prelude::fixed::*;
+//!
+//! let x: DualNumber<f64, char, 2> = 2.0.into_variable_with_key('x');
+//! let y: DualNumber<f64, char, 2> = 3.0.into_variable_with_key('y');
+//! let f = x * y;
+//! assert_eq!(f.value(), &6.0);
+//! assert_eq!(f.dual().get(&'x'), Some(&3.0));
+//! assert_eq!(f.dual().get(&'y'), Some(&2.0));
+//! ```
+
+use crate::fluid::{write_flagged, Value};
+use num_traits::Zero;
+use std::{
+    array::from_fn,
+    fmt::{Display, LowerExp},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg},
+};
+
+/// A bound for keys to use with [`Grad`]
+pub trait GradKey: Clone + Eq {}
+impl<T: Clone + Eq> GradKey for T {}
+
+/// Error of inserting a new key into a full [`Grad`],
+/// or of converting a slice longer than the capacity of a heapless gradient
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CapacityError;
+
+impl Display for CapacityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "fixed-capacity gradient is full")
+    }
+}
+
+/// Sparse gradient with at most `CAP` nonzero keys stored inline
+#[derive(Clone, Copy, Debug)]
+pub struct Grad<Key: GradKey, V: Value, const CAP: usize>([Option<(Key, V)>; CAP]);
+
+impl<Key: GradKey, V: Value, const CAP: usize> Grad<Key, V, CAP> {
+    /// Compile-time capacity
+    pub const CAPACITY: usize = CAP;
+
+    /// Slot holding the key or the first vacant slot
+    fn slot(&self, key: &Key) -> Option<usize> {
+        self.0
+            .iter()
+            .position(|slot| slot.as_ref().is_none_or(|(existing, _)| existing == key))
+    }
+
+    /// Derivative w.r.t. the given key
+    #[must_use]
+    pub fn get(&self, key: &Key) -> Option<&V> {
+        self.iter()
+            .find_map(|(existing, value)| (existing == key).then_some(value))
+    }
+
+    /// Insert a derivative or replace the existing one
+    ///
+    /// # Errors
+    /// Returns [`CapacityError`] if the key is new and all slots are occupied
+    pub fn try_insert(&mut self, key: Key, value: V) -> Result<(), CapacityError> {
+        let index = self.slot(&key).ok_or(CapacityError)?;
+        let slot = self.0.get_mut(index).ok_or(CapacityError)?;
+        *slot = Some((key, value));
+        Ok(())
+    }
+
+    /// Add derivatives of another gradient
+    ///
+    /// # Errors
+    /// Returns [`CapacityError`] if the result does not fit into `CAP` keys.
+    /// In this case, `self` is left unchanged
+    pub fn try_add_assign(&mut self, rhs: &Self) -> Result<(), CapacityError> {
+        self.try_axpy(V::one(), rhs)
+    }
+
+    /// Add derivatives of another gradient scaled by `alpha`
+    ///
+    /// # Errors
+    /// Returns [`CapacityError`] if the result does not fit into `CAP` keys.
+    /// In this case, `self` is left unchanged
+    pub fn try_axpy(&mut self, alpha: V, rhs: &Self) -> Result<(), CapacityError> {
+        let mut out = self.clone();
+        for (key, &value) in rhs.iter() {
+            let index = out.slot(key).ok_or(CapacityError)?;
+            let slot = out.0.get_mut(index).ok_or(CapacityError)?;
+            match slot {
+                Some((_, existing)) => *existing += alpha * value,
+                None => *slot = Some((key.clone(), alpha * value)),
+            }
+        }
+        *self = out;
+        Ok(())
+    }
+
+    /// Stored keys and derivatives
+    pub fn iter(&self) -> impl Iterator<Item = (&Key, &V)> {
+        self.0.iter().flatten().map(|(key, value)| (key, value))
+    }
+
+    /// Number of stored keys
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.iter().flatten().count()
+    }
+
+    /// Whether no keys are stored
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(Option::is_none)
+    }
+}
+
+impl<Key: GradKey, V: Value, const CAP: usize> PartialEq for Grad<Key, V, CAP> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl<Key: GradKey, V: Value, const CAP: usize> Zero for Grad<Key, V, CAP> {
+    fn zero() -> Self {
+        Self(from_fn(|_| None))
+    }
+
+    fn is_zero(&self) -> bool {
+        self.iter().all(|(_, value)| value.is_zero())
+    }
+}
+
+impl<Key: GradKey, V: Value, const CAP: usize> AddAssign for Grad<Key, V, CAP> {
+    /// # Panics
+    /// If the result does not fit into `CAP` keys
+    fn add_assign(&mut self, rhs: Self) {
+        assert!(
+            self.try_add_assign(&rhs).is_ok(),
+            "Capacity {CAP} of fixed gradient should not be exceeded"
+        );
+    }
+}
+
+impl<Key: GradKey, V: Value, const CAP: usize> crate::fluid::Grad<V> for Grad<Key, V, CAP> {
+    /// # Panics
+    /// If the result does not fit into `CAP` keys
+    fn axpy(&mut self, alpha: V, x: &Self) {
+        assert!(
+            self.try_axpy(alpha, x).is_ok(),
+            "Capacity {CAP} of fixed gradient should not be exceeded"
+        );
+    }
+}
+
+impl<Key: GradKey, V: Value, const CAP: usize> Add for Grad<Key, V, CAP> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut out = self;
+        out.add_assign(rhs);
+        out
+    }
+}
+
+impl<Key: GradKey, V: Value, const CAP: usize> MulAssign<V> for Grad<Key, V, CAP> {
+    fn mul_assign(&mut self, rhs: V) {
+        for (_, value) in self.0.iter_mut().flatten() {
+            *value *= rhs;
+        }
+    }
+}
+
+impl<Key: GradKey, V: Value, const CAP: usize> Mul<V> for Grad<Key, V, CAP> {
+    type Output = Self;
+
+    fn mul(self, rhs: V) -> Self::Output {
+        let mut out = self;
+        out.mul_assign(rhs);
+        out
+    }
+}
+
+impl<Key: GradKey, V: Value, const CAP: usize> Neg for Grad<Key, V, CAP> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        let mut out = self;
+        for (_, value) in out.0.iter_mut().flatten() {
+            *value = value.neg();
+        }
+        out
+    }
+}
+
+impl<Key: GradKey, V: Value, const CAP: usize> crate::fluid::GradIter<V> for Grad<Key, V, CAP> {
+    type Key = Key;
+
+    fn derivatives(&self) -> impl Iterator<Item = (Key, V)> + '_ {
+        self.iter().map(|(key, &value)| (key.clone(), value))
+    }
+}
+
+impl<Key: GradKey + Display, V: Value + Display, const CAP: usize> Display for Grad<Key, V, CAP> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "+{{")?;
+        for (index, (key, value)) in self.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{key}: ")?;
+            write_flagged!(f, value, "", "")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl<Key: GradKey + Display, V: Value + LowerExp, const CAP: usize> LowerExp for Grad<Key, V, CAP> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "+{{")?;
+        for (index, (key, value)) in self.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{key}: ")?;
+            write_flagged!(f, value, "", "e")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// For sparse gradients of at most `CAP` variables without allocations
+pub type DualNumber<V, Key, const CAP: usize> = crate::solid::DualNumber<V, Grad<Key, V, CAP>>;
+
+/// Auto-implemented trait for creating independent variables with fixed-capacity sparse gradient
+pub trait IntoVariable: Value {
+    /// Create an independent variable with a given key
+    ///
+    /// # Panics
+    /// If `CAP` is zero
+    fn into_variable_with_key<Key: GradKey, const CAP: usize>(
+        self,
+        key: Key,
+    ) -> DualNumber<Self, Key, CAP> {
+        let mut grad = Grad::zero();
+        assert!(
+            grad.try_insert(key, Self::one()).is_ok(),
+            "Capacity of fixed gradient should be positive"
+        );
+        DualNumber::new(self, grad)
+    }
+}
+
+impl<V: Value> IntoVariable for V {}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+    use crate::fluid::Dual;
+
+    #[test]
+    fn try_add_assign_keeps_self_on_overflow() {
+        let mut grad: Grad<u8, f64, 2> = Grad::zero();
+        assert_eq!(grad.try_insert(0, 1.0_f64), Ok(()));
+        assert_eq!(grad.try_insert(1, 2.0_f64), Ok(()));
+        let mut other: Grad<u8, f64, 2> = Grad::zero();
+        assert_eq!(other.try_insert(2, 3.0_f64), Ok(()));
+
+        let before = grad;
+        assert_eq!(grad.try_add_assign(&other), Err(CapacityError));
+        assert_eq!(grad, before);
+    }
+
+    #[test]
+    fn common_keys_share_slot() {
+        let x: DualNumber<f64, u8, 1> = 2.0_f64.into_variable_with_key(0);
+        let f = x * x + x;
+        assert_eq!(f.dual().len(), 1);
+        assert_eq!(f.dual().get(&0), Some(&5.0_f64));
+    }
+
+    #[test]
+    #[should_panic(expected = "Capacity 1")]
+    fn overflow_panics() {
+        let x: DualNumber<f64, u8, 1> = 2.0_f64.into_variable_with_key(0);
+        let y: DualNumber<f64, u8, 1> = 3.0_f64.into_variable_with_key(1);
+        let _overflow = (x + y).value();
+    }
+}
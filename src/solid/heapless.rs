@@ -0,0 +1,187 @@
+//! [`crate::solid::heapless::DualNumber`] for dynamic number of variables without an allocator
+//!
+//! Same as [`crate::solid::vector`] but based on [`heapless::Vec`] with a compile-time capacity.
+//! Operations which would need more than `CAP` derivatives **panic**
+//!
+//!```
+//! use autodj::prelude::heapless::*;
+//!
+//! let values: heapless::Vec<f64, 4> = heapless::Vec::from_slice(&[2.0, 3.0]).unwrap();
+//! let variables = values.into_variables();
+//! let f = variables[0].mul_impl(&variables[1]);
+//! assert_eq!(f.value(), &6.0);
+//! assert_eq!(f.dual().as_ref().as_slice(), &[3.0, 2.0]);
+//! ```
+#![cfg(feature = "heapless")]
+
+use crate::{fluid::Value, solid::fixed::CapacityError};
+use num_traits::Zero;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg};
+
+/// Newtype wrapper for [`heapless::Vec<impl crate::fluid::Value, CAP>`]
+#[derive(Clone, Debug, PartialEq, PartialOrd, Default)]
+pub struct Grad<V: Value, const CAP: usize>(heapless::Vec<V, CAP>);
+
+impl<V: Value, const CAP: usize> AsRef<heapless::Vec<V, CAP>> for Grad<V, CAP> {
+    fn as_ref(&self) -> &heapless::Vec<V, CAP> {
+        &self.0
+    }
+}
+
+impl<V: Value, const CAP: usize> From<heapless::Vec<V, CAP>> for Grad<V, CAP> {
+    fn from(value: heapless::Vec<V, CAP>) -> Self {
+        Self(value)
+    }
+}
+
+impl<V: Value, const CAP: usize> crate::fluid::GradIter<V> for Grad<V, CAP> {
+    type Key = usize;
+
+    fn derivatives(&self) -> impl Iterator<Item = (usize, V)> + '_ {
+        self.0.iter().copied().enumerate()
+    }
+
+    fn dim(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+impl<V: Value, const CAP: usize> TryFrom<&[V]> for Grad<V, CAP> {
+    type Error = CapacityError;
+
+    fn try_from(value: &[V]) -> Result<Self, Self::Error> {
+        heapless::Vec::from_slice(value)
+            .map(Self)
+            .map_err(|()| CapacityError)
+    }
+}
+
+/// For *dynamically*-known number of variables up to `CAP`
+pub type DualNumber<V, const CAP: usize> = crate::solid::DualNumber<V, Grad<V, CAP>>;
+
+impl<V: Value, const CAP: usize> AddAssign for Grad<V, CAP> {
+    fn add_assign(&mut self, rhs: Self) {
+        let len = self.0.len().max(rhs.0.len());
+        assert!(
+            self.0.resize(len, V::zero()).is_ok(),
+            "The longest operand should fit into capacity {CAP}"
+        );
+        for (to, from) in self.0.iter_mut().zip(rhs.0) {
+            *to += from;
+        }
+    }
+}
+
+impl<V: Value, const CAP: usize> crate::fluid::Grad<V> for Grad<V, CAP> {
+    fn axpy(&mut self, alpha: V, x: &Self) {
+        let len = self.0.len().max(x.0.len());
+        assert!(
+            self.0.resize(len, V::zero()).is_ok(),
+            "The longest operand should fit into capacity {CAP}"
+        );
+        for (to, &from) in self.0.iter_mut().zip(&x.0) {
+            *to += alpha * from;
+        }
+    }
+}
+
+impl<V: Value, const CAP: usize> MulAssign<V> for Grad<V, CAP> {
+    fn mul_assign(&mut self, rhs: V) {
+        for elem in &mut self.0 {
+            *elem *= rhs;
+        }
+    }
+}
+
+impl<V: Value, const CAP: usize> Mul<V> for Grad<V, CAP> {
+    type Output = Self;
+
+    fn mul(self, rhs: V) -> Self::Output {
+        let mut result = self;
+        result *= rhs;
+        result
+    }
+}
+
+impl<V: Value, const CAP: usize> Neg for Grad<V, CAP> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        let mut result = self;
+        for elem in &mut result.0 {
+            *elem = elem.neg();
+        }
+        result
+    }
+}
+
+impl<V: Value, const CAP: usize> Add for Grad<V, CAP> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut result = self;
+        result += rhs;
+        result
+    }
+}
+
+impl<V: Value, const CAP: usize> Zero for Grad<V, CAP> {
+    fn zero() -> Self {
+        Self(heapless::Vec::new())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.iter().all(Zero::is_zero)
+    }
+}
+
+/// Turn [`heapless::Vec`] of [`crate::fluid::Value`] into independent dual variables
+pub trait IntoVariables<V: Value, const CAP: usize>: Into<heapless::Vec<V, CAP>> {
+    /// Turn [`heapless::Vec`] of [`crate::fluid::Value`] into independent dual variables
+    fn into_variables(self) -> heapless::Vec<DualNumber<V, CAP>, CAP> {
+        let values: heapless::Vec<V, CAP> = self.into();
+        let len = values.len();
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let grad = (0..len)
+                    .map(|grad_index| {
+                        if grad_index == index {
+                            V::one()
+                        } else {
+                            V::zero()
+                        }
+                    })
+                    .collect::<heapless::Vec<V, CAP>>();
+                DualNumber::new(value, grad.into())
+            })
+            .collect()
+    }
+}
+impl<V: Value, const CAP: usize, IntoVec> IntoVariables<V, CAP> for IntoVec where
+    IntoVec: Into<heapless::Vec<V, CAP>>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_assign() {
+        let a: Grad<f64, 3> = [1.0_f64, 2.0_f64, 3.0_f64]
+            .as_slice()
+            .try_into()
+            .unwrap_or_default();
+        let mut b: Grad<f64, 3> = [0.5_f64, 1.0_f64].as_slice().try_into().unwrap_or_default();
+        b += a;
+        assert_eq!(b.as_ref().as_slice(), &[1.5_f64, 3.0_f64, 3.0_f64]);
+    }
+
+    #[test]
+    fn overflowing_slice() {
+        let grad: Result<Grad<f64, 1>, _> = [1.0_f64, 2.0_f64].as_slice().try_into();
+        assert_eq!(grad, Err(CapacityError));
+    }
+}
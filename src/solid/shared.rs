@@ -0,0 +1,188 @@
+//! [`crate::solid::shared::DualNumber`] with copy-on-write gradient storage
+//!
+//! Any [`crate::fluid::Grad`] implementor can be wrapped into [`Grad`] behind an [`Arc`].
+//! Clones of dual numbers share their gradients,
+//! and a gradient is cloned only when a shared one is about to be mutated.
+//! This reduces memory traffic for large gradients in deep expression chains
+//!
+//!```
+//! use autodj::prelude::vector::*;
+//! use autodj::solid::shared::IntoShared;
+//!
+//! let [x, y]: [_; 2] = vec![2.0, 3.0].into_variables().try_into().unwrap();
+//! let (x, y) = (x.into_shared(), y.into_shared());
+//!
+//! let x_copy = x.clone();
+//! assert!(x_copy.dual().is_shared());
+//!
+//! let f = x * y;
+//! assert_eq!(f.value(), &6.0);
+//! assert_eq!(f.dual().as_ref().as_ref(), &vec![3.0, 2.0]);
+//! assert!(!x_copy.dual().is_shared());
+//! ```
+#![cfg(feature = "alloc")]
+
+use crate::fluid::{Dual, Value};
+use num_traits::Zero;
+use std::{
+    fmt::{Display, LowerExp},
+    mem::replace,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg},
+    sync::Arc,
+};
+
+/// Copy-on-write wrapper of another gradient
+#[derive(Clone, Debug, Default)]
+pub struct Grad<G>(Arc<G>);
+
+impl<G> Grad<G> {
+    /// Whether the gradient storage is shared with other clones
+    #[must_use]
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.0) > 1
+    }
+}
+
+impl<G: Clone> Grad<G> {
+    /// Unwrap the gradient, cloning it only if shared
+    #[must_use]
+    pub fn into_inner(self) -> G {
+        Arc::try_unwrap(self.0).unwrap_or_else(|shared| shared.as_ref().clone())
+    }
+}
+
+impl<G> AsRef<G> for Grad<G> {
+    fn as_ref(&self) -> &G {
+        &self.0
+    }
+}
+
+impl<G> From<G> for Grad<G> {
+    fn from(value: G) -> Self {
+        Self(Arc::new(value))
+    }
+}
+
+impl<V: Value, G: crate::fluid::GradIter<V>> crate::fluid::GradIter<V> for Grad<G> {
+    type Key = G::Key;
+
+    fn derivatives(&self) -> impl Iterator<Item = (Self::Key, V)> + '_ {
+        self.0.derivatives()
+    }
+
+    fn dim(&self) -> Option<usize> {
+        self.0.dim()
+    }
+}
+
+impl<G: PartialEq> PartialEq for Grad<G> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || self.0 == other.0
+    }
+}
+
+impl<G: AddAssign + Clone> AddAssign for Grad<G> {
+    fn add_assign(&mut self, rhs: Self) {
+        Arc::make_mut(&mut self.0).add_assign(rhs.into_inner());
+    }
+}
+
+impl<G: AddAssign + Clone> Add for Grad<G> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut result = self;
+        result += rhs;
+        result
+    }
+}
+
+impl<V: Value, G: crate::fluid::Grad<V>> crate::fluid::Grad<V> for Grad<G> {
+    fn axpy(&mut self, alpha: V, x: &Self) {
+        Arc::make_mut(&mut self.0).axpy(alpha, &x.0);
+    }
+}
+
+impl<V: Value, G: MulAssign<V> + Clone> MulAssign<V> for Grad<G> {
+    fn mul_assign(&mut self, rhs: V) {
+        Arc::make_mut(&mut self.0).mul_assign(rhs);
+    }
+}
+
+impl<V: Value, G: MulAssign<V> + Clone> Mul<V> for Grad<G> {
+    type Output = Self;
+
+    fn mul(self, rhs: V) -> Self::Output {
+        let mut result = self;
+        result *= rhs;
+        result
+    }
+}
+
+impl<G: Neg<Output = G> + Zero + Clone> Neg for Grad<G> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        let mut result = self;
+        let inner = Arc::make_mut(&mut result.0);
+        *inner = replace(inner, G::zero()).neg();
+        result
+    }
+}
+
+impl<G: Zero + AddAssign + Clone> Zero for Grad<G> {
+    fn zero() -> Self {
+        G::zero().into()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl<G: Display> Display for Grad<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<G: LowerExp> LowerExp for Grad<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// For gradients of type `G` shared between clones
+pub type DualNumber<V, G> = crate::solid::DualNumber<V, Grad<G>>;
+
+/// Auto-implemented trait for wrapping gradients of dual numbers into copy-on-write storage
+pub trait IntoShared<V: Value, G: crate::fluid::Grad<V>> {
+    /// Wrap the gradient into copy-on-write storage
+    fn into_shared(self) -> DualNumber<V, G>;
+}
+
+impl<V: Value, G: crate::fluid::Grad<V>> IntoShared<V, G> for crate::solid::DualNumber<V, G> {
+    fn into_shared(self) -> DualNumber<V, G> {
+        let (value, grad) = self.decompose();
+        DualNumber::new(value, grad.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solid::vector;
+    use std::prelude::v1::vec;
+
+    #[test]
+    fn mutation_detaches_clone() {
+        let grad: Grad<vector::Grad<f64>> = vector::Grad::from(vec![1.0_f64, 2.0_f64]).into();
+        let copy = grad.clone();
+        assert!(grad.is_shared());
+
+        let negated = -grad;
+        assert!(!copy.is_shared());
+        assert_eq!(copy.as_ref().as_ref(), &vec![1.0_f64, 2.0_f64]);
+        assert_eq!(negated.as_ref().as_ref(), &vec![-1.0_f64, -2.0_f64]);
+    }
+}
@@ -0,0 +1,203 @@
+//! [`crate::solid::simd::DualNumber`] with gradients in SIMD lanes of [`wide::f64x4`]
+//!
+//! Same as [`crate::solid::array`] for [`f64`] values,
+//! but derivatives are packed by four, so gradient updates compile to vector instructions.
+//! Gradients hold `4 * LANES` derivatives, and extra ones stay zero.
+//! Turning more than `4 * LANES` values into variables **panics**
+//!
+//!```
+//! use autodj::prelude::simd::*;
+//!
+//! let [x, y, z]: [DualNumber<1>; 3] = [2.0, 3.0, 4.0].into_variables();
+//! let f = x * y + z;
+//! assert_eq!(f.value(), &10.0);
+//! assert_eq!(f.dual().to_vec(), [3.0, 2.0, 1.0, 0.0]);
+//! ```
+#![cfg(feature = "simd")]
+
+use num_traits::Zero;
+#[cfg(feature = "alloc")]
+use std::prelude::v1::Vec;
+use std::{
+    array::from_fn,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg},
+};
+use wide::f64x4;
+
+/// Number of derivatives in a lane
+const WIDTH: usize = 4;
+
+/// Derivatives packed into `LANES` SIMD lanes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Grad<const LANES: usize>([f64x4; LANES]);
+
+impl<const LANES: usize> Default for Grad<LANES> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<const LANES: usize> AsRef<[f64x4; LANES]> for Grad<LANES> {
+    fn as_ref(&self) -> &[f64x4; LANES] {
+        &self.0
+    }
+}
+
+impl<const LANES: usize> From<[f64x4; LANES]> for Grad<LANES> {
+    fn from(value: [f64x4; LANES]) -> Self {
+        Self(value)
+    }
+}
+
+impl<const LANES: usize> Grad<LANES> {
+    /// Number of derivatives held
+    pub const CAPACITY: usize = WIDTH * LANES;
+
+    /// Derivative with respect to a variable. [`None`] beyond [`Self::CAPACITY`]
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<f64> {
+        self.0
+            .get(index / WIDTH)
+            .and_then(|lane| lane.as_array_ref().get(index % WIDTH))
+            .copied()
+    }
+
+    /// Unpack all derivatives
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<f64> {
+        self.0.iter().flat_map(|lane| lane.to_array()).collect()
+    }
+}
+
+impl<const LANES: usize> crate::fluid::GradIter<f64> for Grad<LANES> {
+    type Key = usize;
+
+    fn derivatives(&self) -> impl Iterator<Item = (usize, f64)> + '_ {
+        self.0.iter().flat_map(|lane| lane.to_array()).enumerate()
+    }
+
+    fn dim(&self) -> Option<usize> {
+        Some(Self::CAPACITY)
+    }
+}
+
+/// For *statically*-known number of variables up to `4 * LANES`
+pub type DualNumber<const LANES: usize> = crate::solid::DualNumber<f64, Grad<LANES>>;
+
+impl<const LANES: usize> AddAssign for Grad<LANES> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (to, from) in self.0.iter_mut().zip(rhs.0) {
+            *to = *to + from;
+        }
+    }
+}
+
+impl<const LANES: usize> crate::fluid::Grad<f64> for Grad<LANES> {
+    fn axpy(&mut self, alpha: f64, x: &Self) {
+        for (to, &from) in self.0.iter_mut().zip(&x.0) {
+            *to += from * alpha;
+        }
+    }
+}
+
+impl<const LANES: usize> MulAssign<f64> for Grad<LANES> {
+    fn mul_assign(&mut self, rhs: f64) {
+        for lane in &mut self.0 {
+            *lane = *lane * rhs;
+        }
+    }
+}
+
+impl<const LANES: usize> Mul<f64> for Grad<LANES> {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        let mut result = self;
+        result *= rhs;
+        result
+    }
+}
+
+impl<const LANES: usize> Neg for Grad<LANES> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(self.0.map(|lane| f64x4::ZERO - lane))
+    }
+}
+
+impl<const LANES: usize> Add for Grad<LANES> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut result = self;
+        result += rhs;
+        result
+    }
+}
+
+impl<const LANES: usize> Zero for Grad<LANES> {
+    fn zero() -> Self {
+        Self([f64x4::ZERO; LANES])
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0
+            .iter()
+            .all(|lane| lane.to_array().iter().all(Zero::is_zero))
+    }
+}
+
+/// Turn array of [`f64`] into independent dual variables
+pub trait IntoVariables<const N: usize> {
+    /// Turn array of [`f64`] into independent dual variables.
+    /// Fails to compile if `N` exceeds `4 * LANES`
+    fn into_variables<const LANES: usize>(self) -> [DualNumber<LANES>; N];
+}
+
+impl<const N: usize> IntoVariables<N> for [f64; N] {
+    fn into_variables<const LANES: usize>(self) -> [DualNumber<LANES>; N] {
+        const { assert!(N <= WIDTH * LANES, "variables must fit into lanes") };
+        from_fn(|index| {
+            let grad = from_fn(|lane| {
+                f64x4::from(from_fn::<f64, WIDTH, _>(|offset| {
+                    if lane * WIDTH + offset == index {
+                        1.0_f64
+                    } else {
+                        0.0_f64
+                    }
+                }))
+            });
+            let value = self.get(index).copied().unwrap_or_default();
+            DualNumber::new(value, Grad(grad))
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+    use crate::fluid::Dual;
+
+    #[test]
+    fn matches_array_gradient() {
+        let values = [1.0_f64, 2.0_f64, 3.0_f64, 4.0_f64, 5.0_f64, 6.0_f64];
+        let simd = IntoVariables::into_variables::<2>(values)
+            .into_iter()
+            .fold(DualNumber::parameter(1.0_f64), |acc, x| acc * x);
+        let array = crate::solid::array::IntoVariables::into_variables(values)
+            .into_iter()
+            .fold(
+                crate::solid::array::DualNumber::parameter(1.0_f64),
+                |acc, x| acc * x,
+            );
+        assert_eq!(simd.value(), array.value());
+        assert!(
+            (0..6).all(|index| simd.dual().get(index) == array.dual().as_ref().get(index).copied())
+        );
+        assert_eq!(simd.dual().get(7), Some(0.0_f64));
+        assert_eq!(simd.dual().get(8), None);
+    }
+}
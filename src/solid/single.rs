@@ -1,6 +1,11 @@
 //! [`crate::solid::DualNumber`] for single variable differentiations
 
-use crate::fluid::{Dual, Value};
+use crate::{
+    fluid::{Dual, Value},
+    solid::ParseDualError,
+};
+use num_traits::ConstZero;
+use std::str::FromStr;
 
 /// Uni-variate dual number
 ///
@@ -20,6 +25,34 @@ pub type DualF64 = DualNumber<f64>;
 /// Single [`f32`] variable
 pub type DualF32 = DualNumber<f32>;
 
+/// Parses the [`Display`](std::fmt::Display) format back
+///```
+/// use autodj::prelude::single::*;
+///
+/// let f = 3.0.into_variable().powf(2.0) - 20.0;
+/// let parsed: DualF64 = f.to_string().parse().unwrap();
+/// assert_eq!(parsed, f);
+/// assert_eq!("-0.5-2∆".parse(), Ok(DualF64::new(-0.5, -2.0)));
+/// assert!("1+2".parse::<DualF64>().is_err());
+/// ```
+impl<V: Value + FromStr> FromStr for DualNumber<V> {
+    type Err = ParseDualError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let body = s.strip_suffix('∆').ok_or(ParseDualError)?;
+        // the derivative starts with its sign, unless it is NaN
+        let (value, deriv) = match body.get(1..).and_then(|rest| rest.find(['+', '-'])) {
+            Some(index) => body.split_at_checked(index + 1).ok_or(ParseDualError)?,
+            None => (body.strip_suffix("NaN").ok_or(ParseDualError)?, "NaN"),
+        };
+        let deriv = deriv.strip_prefix('+').unwrap_or(deriv);
+        Ok(Self::new(
+            value.parse().map_err(|_| ParseDualError)?,
+            deriv.parse().map_err(|_| ParseDualError)?,
+        ))
+    }
+}
+
 // TODO: is it generalizable for multivariate ?
 /// Create an independent variable from a value
 pub trait IntoVariable: Value {
@@ -30,3 +63,44 @@ pub trait IntoVariable: Value {
 }
 
 impl<V: Value> IntoVariable for V {}
+
+/// Same as [`Dual::parameter`] but usable in constant expressions
+///```
+/// use autodj::prelude::single::*;
+/// use autodj::solid::single;
+///
+/// const OFFSET: DualF64 = single::parameter(1.0);
+/// static SLOPES: [DualF32; 2] = [DualF32::new(0.0, 1.0), DualF32::new(0.0, 2.0)];
+///
+/// assert_eq!((3.0.into_variable() - OFFSET).decompose(), (2.0, 1.0));
+/// assert_eq!(SLOPES[1].dual(), &2.0);
+/// ```
+#[must_use]
+pub const fn parameter<V: Value + ConstZero>(value: V) -> DualNumber<V> {
+    DualNumber::new(value, V::ZERO)
+}
+
+/// Value and derivative of a scalar function at a point
+pub fn differentiate<V, F>(func: F, at: V) -> (V, V)
+where
+    V: Value,
+    F: FnOnce(DualNumber<V>) -> DualNumber<V>,
+{
+    func(at.into_variable()).decompose()
+}
+
+/// Value and derivative of a closure over [`single::DualNumber`](crate::solid::single::DualNumber)
+/// for quick exploratory use. Methods of [`Dual`] are in scope within the closure
+///```
+/// let (value, deriv) = autodj::derivative!(|x| x.sin() * x, at = 2.0);
+/// assert_eq!(value, 2.0_f64.sin() * 2.0);
+/// assert_eq!(deriv, 2.0_f64.cos() * 2.0 + 2.0_f64.sin());
+/// ```
+#[macro_export]
+macro_rules! derivative {
+    ($func:expr, at = $at:expr $(,)?) => {{
+        #[allow(unused_imports)]
+        use $crate::fluid::Dual as _;
+        $crate::solid::single::differentiate($func, $at)
+    }};
+}
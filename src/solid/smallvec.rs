@@ -0,0 +1,160 @@
+//! [`crate::solid::smallvec::DualNumber`] for dynamic number of variables stored inline while few
+//!
+//! Same as [`crate::solid::vector`] but based on [`smallvec::SmallVec`]:
+//! up to `N` derivatives are kept on the stack, and larger gradients spill to the heap
+//!
+//!```
+//! use autodj::prelude::smallvec::*;
+//!
+//! let variables: SmallVec<[DualNumber<f64, 4>; 4]> = [2.0, 3.0].as_slice().into_variables();
+//! let f = variables[0].mul_impl(&variables[1]);
+//! assert_eq!(f.value(), &6.0);
+//! assert_eq!(f.dual().as_ref().as_slice(), &[3.0, 2.0]);
+//! assert!(!f.dual().as_ref().spilled());
+//! ```
+#![cfg(feature = "smallvec")]
+
+use crate::fluid::Value;
+use num_traits::Zero;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg};
+
+pub use smallvec::SmallVec;
+
+/// Newtype wrapper for [`SmallVec<[impl crate::fluid::Value; N]>`]
+#[derive(Clone, Debug, PartialEq, PartialOrd, Default)]
+pub struct Grad<V: Value, const N: usize>(SmallVec<[V; N]>);
+
+impl<V: Value, const N: usize> AsRef<SmallVec<[V; N]>> for Grad<V, N> {
+    fn as_ref(&self) -> &SmallVec<[V; N]> {
+        &self.0
+    }
+}
+
+impl<V: Value, const N: usize, IntoSmallVec: Into<SmallVec<[V; N]>>> From<IntoSmallVec>
+    for Grad<V, N>
+{
+    fn from(value: IntoSmallVec) -> Self {
+        Self(value.into())
+    }
+}
+
+impl<V: Value, const N: usize> crate::fluid::GradIter<V> for Grad<V, N> {
+    type Key = usize;
+
+    fn derivatives(&self) -> impl Iterator<Item = (usize, V)> + '_ {
+        self.0.iter().copied().enumerate()
+    }
+
+    fn dim(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+/// For *dynamically*-known number of variables, allocating only beyond `N`
+pub type DualNumber<V, const N: usize> = crate::solid::DualNumber<V, Grad<V, N>>;
+
+impl<V: Value, const N: usize> AddAssign for Grad<V, N> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0.resize(self.0.len().max(rhs.0.len()), V::zero());
+        for (to, from) in self.0.iter_mut().zip(rhs.0) {
+            *to += from;
+        }
+    }
+}
+
+impl<V: Value, const N: usize> crate::fluid::Grad<V> for Grad<V, N> {
+    fn axpy(&mut self, alpha: V, x: &Self) {
+        self.0.resize(self.0.len().max(x.0.len()), V::zero());
+        for (to, &from) in self.0.iter_mut().zip(&x.0) {
+            *to += alpha * from;
+        }
+    }
+}
+
+impl<V: Value, const N: usize> MulAssign<V> for Grad<V, N> {
+    fn mul_assign(&mut self, rhs: V) {
+        for elem in &mut self.0 {
+            *elem *= rhs;
+        }
+    }
+}
+
+impl<V: Value, const N: usize> Mul<V> for Grad<V, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: V) -> Self::Output {
+        let mut result = self;
+        result *= rhs;
+        result
+    }
+}
+
+impl<V: Value, const N: usize> Neg for Grad<V, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        let mut result = self;
+        for elem in &mut result.0 {
+            *elem = elem.neg();
+        }
+        result
+    }
+}
+
+impl<V: Value, const N: usize> Add for Grad<V, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut result = self;
+        result += rhs;
+        result
+    }
+}
+
+impl<V: Value, const N: usize> Zero for Grad<V, N> {
+    fn zero() -> Self {
+        Self(SmallVec::new())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.iter().all(Zero::is_zero)
+    }
+}
+
+/// Turn [`SmallVec`] of [`crate::fluid::Value`] into independent dual variables
+pub trait IntoVariables<V: Value, const N: usize>: Into<SmallVec<[V; N]>> {
+    /// Turn [`SmallVec`] of [`crate::fluid::Value`] into independent dual variables
+    fn into_variables(self) -> SmallVec<[DualNumber<V, N>; N]> {
+        let values: SmallVec<[V; N]> = self.into();
+        let len = values.len();
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let mut grad = SmallVec::from_elem(V::zero(), len);
+                if let Some(deriv) = grad.get_mut(index) {
+                    *deriv = V::one();
+                }
+                DualNumber::new(value, Grad(grad))
+            })
+            .collect()
+    }
+}
+impl<V: Value, const N: usize, IntoSmallVec> IntoVariables<V, N> for IntoSmallVec where
+    IntoSmallVec: Into<SmallVec<[V; N]>>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_assign() {
+        let a: Grad<f64, 2> = [1.0_f64, 2.0_f64, 3.0_f64].as_slice().into();
+        let mut b: Grad<f64, 2> = [0.5_f64, 1.0_f64].into();
+        b += a;
+        assert_eq!(b.as_ref().as_slice(), &[1.5_f64, 3.0_f64, 3.0_f64]);
+        assert!(b.as_ref().spilled());
+    }
+}
@@ -1,18 +1,49 @@
-//! [`crate::sparse::DualNumber`] based on [`HashMap`] for sparse dual components
+//! [`crate::solid::sparse::DualNumber`] based on [`HashMap`] for sparse dual components
+//!
+//! With `hashbrown` feature, [`hashbrown::HashMap`] is used regardless of `std` feature.
+//! Hasher is pluggable via the last generic parameter of [`Grad`]
 #![cfg(feature = "sparse")]
 
-use crate::solid::Value;
+use crate::{
+    fluid::{write_flagged, Dual},
+    solid::Value,
+};
 use std::{
-    collections::HashMap,
-    hash::Hash,
+    borrow::Borrow,
+    fmt::{Display, LowerExp},
+    hash::{BuildHasher, Hash},
     ops::{Add, MulAssign},
+    prelude::v1::Vec,
 };
 
+#[cfg(feature = "hashbrown")]
+pub use hashbrown::{DefaultHashBuilder as DefaultHasher, HashMap};
+
+#[cfg(not(feature = "hashbrown"))]
+pub use std::collections::HashMap;
+
+/// Default hasher of [`HashMap`] in use
+#[cfg(not(feature = "hashbrown"))]
+pub type DefaultHasher = <HashMap<u8, u8> as HashMapHasher>::Hasher;
+
+/// To extract default hasher from the [`HashMap`] in use
+#[cfg(not(feature = "hashbrown"))]
+pub trait HashMapHasher {
+    /// Hasher of the map
+    type Hasher;
+}
+
+#[cfg(not(feature = "hashbrown"))]
+impl<K, V, S> HashMapHasher for HashMap<K, V, S> {
+    type Hasher = S;
+}
+
 /// Merge two maps. For common keys, merge values using given binary operation
-fn merge_assign<K, V, F>(map1: &mut HashMap<K, V>, map2: &HashMap<K, V>, op: F)
+fn merge_assign<K, V, S, F>(map1: &mut HashMap<K, V, S>, map2: &HashMap<K, V, S>, op: F)
 where
     K: Eq + Hash + Clone,
     V: Clone,
+    S: BuildHasher,
     F: Fn(V, V) -> V,
 {
     for (key, value) in map2 {
@@ -29,13 +60,37 @@ where
 pub trait GradKey: Clone + Eq + Hash {}
 impl<T: Clone + Eq + Hash> GradKey for T {}
 
+/// A bound for hashers to use with [`Grad`]
+pub trait GradHasher: BuildHasher + Clone + Default {}
+impl<S: BuildHasher + Clone + Default> GradHasher for S {}
+
 /// Sparse gradient for dual numbers
-#[derive(Clone, Debug, PartialEq, Default)]
-pub struct Grad<Key: GradKey, V: Value>(HashMap<Key, V>);
+#[derive(Clone, Debug, Default)]
+pub struct Grad<Key: GradKey, V: Value, S: GradHasher = DefaultHasher>(HashMap<Key, V, S>);
+
+impl<Key: GradKey, V: Value, S: GradHasher> Grad<Key, V, S> {
+    /// Zero gradient with room for `capacity` keys, so it grows without rehashing up to that size
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(HashMap::with_capacity_and_hasher(capacity, S::default()))
+    }
+
+    /// Number of keys the gradient can hold without rehashing
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+}
+
+impl<Key: GradKey, V: Value, S: GradHasher> PartialEq for Grad<Key, V, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
 
-impl<Key: GradKey, V: Value> num_traits::Zero for Grad<Key, V> {
+impl<Key: GradKey, V: Value, S: GradHasher> num_traits::Zero for Grad<Key, V, S> {
     fn zero() -> Self {
-        Self(HashMap::new())
+        Self(HashMap::default())
     }
 
     fn is_zero(&self) -> bool {
@@ -43,7 +98,7 @@ impl<Key: GradKey, V: Value> num_traits::Zero for Grad<Key, V> {
     }
 }
 
-impl<Key: GradKey, V: Value> Add for Grad<Key, V> {
+impl<Key: GradKey, V: Value, S: GradHasher> Add for Grad<Key, V, S> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
@@ -53,7 +108,7 @@ impl<Key: GradKey, V: Value> Add for Grad<Key, V> {
     }
 }
 
-impl<Key: GradKey, V: Value> std::ops::Mul<V> for Grad<Key, V> {
+impl<Key: GradKey, V: Value, S: GradHasher> std::ops::Mul<V> for Grad<Key, V, S> {
     type Output = Self;
 
     fn mul(self, rhs: V) -> Self::Output {
@@ -63,13 +118,13 @@ impl<Key: GradKey, V: Value> std::ops::Mul<V> for Grad<Key, V> {
     }
 }
 
-impl<Key: GradKey, V: Value> MulAssign<V> for Grad<Key, V> {
+impl<Key: GradKey, V: Value, S: GradHasher> MulAssign<V> for Grad<Key, V, S> {
     fn mul_assign(&mut self, rhs: V) {
         self.0.values_mut().for_each(|v| v.mul_assign(rhs));
     }
 }
 
-impl<Key: GradKey, V: Value> std::ops::Neg for Grad<Key, V> {
+impl<Key: GradKey, V: Value, S: GradHasher> std::ops::Neg for Grad<Key, V, S> {
     type Output = Self;
 
     fn neg(self) -> Self::Output {
@@ -79,21 +134,263 @@ impl<Key: GradKey, V: Value> std::ops::Neg for Grad<Key, V> {
     }
 }
 
-impl<Key: GradKey, V: Value> std::ops::AddAssign for Grad<Key, V> {
+impl<Key: GradKey, V: Value, S: GradHasher> std::ops::AddAssign for Grad<Key, V, S> {
     fn add_assign(&mut self, rhs: Self) {
         merge_assign(&mut self.0, &rhs.0, Add::add);
     }
 }
 
-impl<Key: GradKey, V: Value> crate::fluid::Grad<V> for Grad<Key, V> {}
+impl<Key: GradKey, V: Value, S: GradHasher> crate::fluid::Grad<V> for Grad<Key, V, S> {
+    fn axpy(&mut self, alpha: V, x: &Self) {
+        for (key, &value) in &x.0 {
+            *self.0.entry(key.clone()).or_insert_with(V::zero) += alpha * value;
+        }
+    }
+}
 
-impl<Key: GradKey, V: Value> AsRef<HashMap<Key, V>> for Grad<Key, V> {
-    fn as_ref(&self) -> &HashMap<Key, V> {
+impl<Key: GradKey, V: Value, S: GradHasher> AsRef<HashMap<Key, V, S>> for Grad<Key, V, S> {
+    fn as_ref(&self) -> &HashMap<Key, V, S> {
         &self.0
     }
 }
 
+impl<Key: GradKey, V: Value, S: GradHasher> crate::fluid::GradIter<V> for Grad<Key, V, S> {
+    type Key = Key;
+
+    fn derivatives(&self) -> impl Iterator<Item = (Key, V)> + '_ {
+        self.iter().map(|(key, &value)| (key.clone(), value))
+    }
+}
+
+impl<Key: GradKey, V: Value, S: GradHasher> Grad<Key, V, S> {
+    /// Derivative w.r.t. the given key
+    ///```
+    /// use autodj::solid::sparse::Grad;
+    ///
+    /// let grad: Grad<String, f64> = [("x".to_owned(), 1.0)].into_iter().collect();
+    /// assert_eq!(grad.get("x"), Some(&1.0));
+    /// assert_eq!(grad.get("y"), None);
+    /// assert_eq!(grad.len(), 1);
+    /// assert_eq!(grad.iter().next(), Some((&"x".to_owned(), &1.0)));
+    /// ```
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.0.get(key)
+    }
+
+    /// Stored keys and derivatives in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = (&Key, &V)> {
+        self.0.iter()
+    }
+
+    /// Stored keys in arbitrary order
+    pub fn keys(&self) -> impl Iterator<Item = &Key> {
+        self.0.keys()
+    }
+
+    /// Number of stored keys
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether no keys are stored
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Dense gradient ordered by given keys. Absent keys give zeros
+    ///```
+    /// use autodj::solid::sparse::Grad;
+    ///
+    /// let grad: Grad<char, f64> = [('x', 1.0), ('y', 2.0)].into_iter().collect();
+    /// assert_eq!(grad.to_dense(&['y', 'z', 'x']), [2.0, 0.0, 1.0]);
+    /// ```
+    #[must_use]
+    pub fn to_dense(&self, order: &[Key]) -> Vec<V> {
+        order
+            .iter()
+            .map(|key| self.0.get(key).copied().unwrap_or_else(V::zero))
+            .collect()
+    }
+}
+
+impl<Key: GradKey + Ord, V: Value, S: GradHasher> Grad<Key, V, S> {
+    /// Entries sorted by keys
+    fn sorted(&self) -> Vec<(&Key, &V)> {
+        let mut entries: Vec<_> = self.0.iter().collect();
+        entries.sort_unstable_by_key(|&(key, _)| key);
+        entries
+    }
+}
+
+/// Entries are sorted by keys
+///```
+/// use autodj::solid::sparse::Grad;
+///
+/// let grad: Grad<char, f64> = [('y', 2.0), ('x', 1.5)].into_iter().collect();
+/// assert_eq!(format!("{grad}"), "+{x: 1.5, y: 2.0}");
+/// assert_eq!(format!("{grad:e}"), "+{x: 1.5e0, y: 2e0}");
+/// ```
+impl<Key: GradKey + Ord + Display, V: Value, S: GradHasher> Display for Grad<Key, V, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "+{{")?;
+        for (index, (key, value)) in self.sorted().into_iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{key}: ")?;
+            write_flagged!(f, value, "", "?")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl<Key: GradKey + Ord + Display, V: Value + LowerExp, S: GradHasher> LowerExp
+    for Grad<Key, V, S>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "+{{")?;
+        for (index, (key, value)) in self.sorted().into_iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{key}: ")?;
+            write_flagged!(f, value, "", "e")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// Sparse gradient from dense derivatives paired with keys. Zero derivatives are omitted
+pub(crate) fn from_dense<Key: GradKey, V: Value>(derivs: &[V], keys: &[Key]) -> Grad<Key, V> {
+    keys.iter()
+        .zip(derivs)
+        .filter(|(_, deriv)| !deriv.is_zero())
+        .map(|(key, &deriv)| (key.clone(), deriv))
+        .collect()
+}
+
+impl<Key: GradKey, V: Value, S: GradHasher> FromIterator<(Key, V)> for Grad<Key, V, S> {
+    fn from_iter<T: IntoIterator<Item = (Key, V)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
 /// For sparse gradients
-pub type DualNumber<V, Key> = crate::solid::DualNumber<V, Grad<Key, V>>;
+pub type DualNumber<V, Key, S = DefaultHasher> = crate::solid::DualNumber<V, Grad<Key, V, S>>;
+
+impl<V: Value, Key: GradKey, S: GradHasher> DualNumber<V, Key, S> {
+    /// Zero parameter with room for `capacity` keys, e.g. an accumulator in a hot loop
+    ///```
+    /// use autodj::prelude::indexed::*;
+    ///
+    /// let x = [1.0, 2.0, 3.0].map(IntoVariable::into_variable);
+    /// let mut total = DualF64::with_grad_capacity(x.len());
+    /// for xi in &x {
+    ///     let _ = total.add_assign_impl(xi);
+    /// }
+    /// assert!(total.dual().capacity() >= 3);
+    /// assert_eq!(total.value(), &6.0);
+    /// ```
+    #[must_use]
+    pub fn with_grad_capacity(capacity: usize) -> Self {
+        Self::new(V::zero(), Grad::with_capacity(capacity))
+    }
+}
+
+/// Registry of independent sparse variables which remembers the order of their keys.
+///
+/// Provides dense gradients and Jacobians with deterministic column ordering.
+/// Derivatives w.r.t. keys unknown to the registry are ignored
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariableSpace<Key: GradKey> {
+    /// keys in order of registration
+    keys: Vec<Key>,
+    /// positions of keys
+    positions: HashMap<Key, usize>,
+}
+
+impl<Key: GradKey> Default for VariableSpace<Key> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Key: GradKey> VariableSpace<Key> {
+    /// Construct an empty registry
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            keys: Vec::new(),
+            positions: HashMap::new(),
+        }
+    }
+
+    /// Registered keys in order of registration
+    #[must_use]
+    pub fn keys(&self) -> &[Key] {
+        &self.keys
+    }
+
+    /// Number of registered variables
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Whether no variables are registered
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Column of a registered key
+    #[must_use]
+    pub fn position(&self, key: &Key) -> Option<usize> {
+        self.positions.get(key).copied()
+    }
+
+    /// Register a key (if not yet) and create an independent variable with it
+    pub fn variable_with_key<V: Value>(&mut self, key: Key, value: V) -> DualNumber<V, Key> {
+        if !self.positions.contains_key(&key) {
+            let _unused = self.positions.insert(key.clone(), self.keys.len());
+            self.keys.push(key.clone());
+        }
+        DualNumber::new(value, [(key, V::one())].into_iter().collect())
+    }
+
+    /// Dense gradient ordered by registration
+    #[must_use]
+    pub fn gradient<V: Value, S: GradHasher>(&self, grad: &Grad<Key, V, S>) -> Vec<V> {
+        grad.to_dense(&self.keys)
+    }
+
+    /// Row-major dense Jacobian: a row per dual number, a column per registered variable
+    #[must_use]
+    pub fn jacobian<V: Value, S: GradHasher>(&self, rows: &[DualNumber<V, Key, S>]) -> Vec<V> {
+        rows.iter()
+            .flat_map(|row| self.gradient(row.dual()))
+            .collect()
+    }
+
+    /// Dense Jacobian as [`nalgebra::DMatrix`]
+    #[cfg(feature = "nalgebra")]
+    #[must_use]
+    pub fn jacobian_matrix<V, S>(&self, rows: &[DualNumber<V, Key, S>]) -> nalgebra::DMatrix<V>
+    where
+        V: Value + nalgebra::Scalar,
+        S: GradHasher,
+    {
+        nalgebra::DMatrix::from_row_slice(rows.len(), self.len(), &self.jacobian(rows))
+    }
+}
 
+pub mod indexed;
+pub mod named;
 pub mod uuid;
@@ -0,0 +1,77 @@
+//! [`crate::solid::sparse::DualNumber`] specialization with [`usize`] keys to distinguish different variables
+//!
+//! Keys are handed out either by a global atomic counter ([`IntoVariable`])
+//! or by an explicit [`VariableFactory`].
+//! Neither requires random number generation, so both work on bare-metal targets.
+//!
+//!```
+//! use autodj::prelude::indexed::*;
+//!
+//! let mut factory = VariableFactory::new();
+//! let x = factory.variable(2.0);
+//! let y = factory.variable(3.0);
+//! let f = x.mul_impl(&y);
+//! assert_eq!(f.dual().as_ref().get(&0), Some(&3.0));
+//! assert_eq!(f.dual().as_ref().get(&1), Some(&2.0));
+//! ```
+
+use crate::fluid::Value;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Sparse dual number based on [`usize`] keys
+pub type DualNumber<V> = crate::solid::sparse::DualNumber<V, usize>;
+
+/// Specialization for [`f64`]
+pub type DualF64 = DualNumber<f64>;
+
+/// Specialization for [`f32`]
+pub type DualF32 = DualNumber<f32>;
+
+/// Global source of unique keys for [`IntoVariable`]
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Create an independent variable with a given key
+fn variable_with_key<V: Value>(key: usize, value: V) -> DualNumber<V> {
+    DualNumber::new(value, [(key, V::one())].into_iter().collect())
+}
+
+/// Auto-implemented trait for creating independent variables with sparse gradient
+pub trait IntoVariable: Value {
+    /// Create sparse dual number with a key from the global atomic counter
+    fn into_variable(self) -> DualNumber<Self> {
+        variable_with_key(COUNTER.fetch_add(1, Ordering::Relaxed), self)
+    }
+}
+
+impl<V: Value> IntoVariable for V {}
+
+/// Explicit source of consecutive keys starting from zero.
+///
+/// Keys of different factories may coincide,
+/// so variables from different factories should not be mixed
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct VariableFactory {
+    /// key for the next variable
+    next: usize,
+}
+
+impl VariableFactory {
+    /// Construct a factory starting from zero key
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an independent variable with the next key
+    pub fn variable<V: Value>(&mut self, value: V) -> DualNumber<V> {
+        let key = self.next;
+        self.next += 1;
+        variable_with_key(key, value)
+    }
+
+    /// Number of created variables
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.next
+    }
+}
@@ -0,0 +1,43 @@
+//! [`crate::solid::sparse::DualNumber`] specialization with human-readable names as keys
+//!
+//! Gradients can be reported by parameter names instead of opaque identifiers.
+//! It is up to the user to keep the names unique
+//!
+//!```
+//! use autodj::prelude::named::*;
+//!
+//! let alpha = 2.0.into_variable_named("alpha");
+//! let beta = 3.0.into_variable_named("beta");
+//! let f = alpha.mul_impl(&beta);
+//! assert_eq!(f.dual().as_ref().get("alpha"), Some(&3.0));
+//! assert_eq!(f.dual().as_ref().get("beta"), Some(&2.0));
+//! ```
+
+use crate::{fluid::Value, solid::sparse::GradKey};
+use std::prelude::v1::String;
+
+/// Sparse dual number based on [`String`] keys
+pub type DualNumber<V> = crate::solid::sparse::DualNumber<V, String>;
+
+/// Specialization for [`f64`]
+pub type DualF64 = DualNumber<f64>;
+
+/// Specialization for [`f32`]
+pub type DualF32 = DualNumber<f32>;
+
+/// Sparse dual number based on `&'static str` keys
+pub type StaticDualNumber<V> = crate::solid::sparse::DualNumber<V, &'static str>;
+
+/// Auto-implemented trait for creating named independent variables with sparse gradient
+pub trait IntoVariable: Value {
+    /// Create sparse dual number with a given name
+    /// (e.g. [`String`] or `&'static str`)
+    fn into_variable_named<Name: GradKey>(
+        self,
+        name: Name,
+    ) -> crate::solid::sparse::DualNumber<Self, Name> {
+        crate::solid::sparse::DualNumber::new(self, [(name, Self::one())].into_iter().collect())
+    }
+}
+
+impl<V: Value> IntoVariable for V {}
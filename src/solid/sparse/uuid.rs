@@ -1,25 +1,119 @@
-//! [`crate::sparse::DualNumber`] specialization with [`::uuid::Uuid`] as keys to distinguish different variables
+//! [`crate::solid::sparse::DualNumber`] specialization with [`::uuid::Uuid`] as keys to distinguish different variables
 #![cfg(feature = "uuid")]
 
 use crate::{
     fluid::{Dual, Value},
-    solid::sparse::Grad,
+    solid::sparse::VariableSpace,
 };
 use ::uuid::Uuid;
-use std::collections::HashMap;
+use std::prelude::v1::Vec;
 
 /// Sparse dual number based on [`uuid::Uuid`] keys
 pub type DualNumber<V> = crate::solid::sparse::DualNumber<V, Uuid>;
 
+/// Specialization for [`f64`]
+pub type DualF64 = DualNumber<f64>;
+
+/// Specialization for [`f32`]
+pub type DualF32 = DualNumber<f32>;
+
 /// Auto-implemented trait for creating independent variables with sparse gradient
 pub trait IntoVariable: Value {
     /// Create sparse dual number from some [`crate::fluid::Value`] implementor
     fn into_variable(self) -> DualNumber<Self> {
-        let grad_id = Uuid::new_v4();
+        self.into_variable_with_id(Uuid::new_v4())
+    }
+
+    /// Create sparse dual number with a user-supplied key
+    fn into_variable_with_id(self, grad_id: Uuid) -> DualNumber<Self> {
         let grad_value = Self::one();
-        let grad = [(grad_id, grad_value)].into_iter().collect::<HashMap<_, _>>();
-        DualNumber::<Self>::new(self, Grad(grad))
+        let grad = [(grad_id, grad_value)].into_iter().collect();
+        DualNumber::<Self>::new(self, grad)
     }
 }
 
 impl<V: Value> IntoVariable for V {}
+
+/// Reusable key of a logical variable to keep it across evaluations
+///```
+/// use autodj::prelude::uuid::*;
+///
+/// let handle = VariableHandle::new();
+/// let first = handle.variable(1.0).powf(2.0);
+/// let second = handle.variable(2.0).powf(2.0);
+/// assert_eq!(handle.derivative(&first), 2.0);
+/// assert_eq!(handle.derivative(&second), 4.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VariableHandle(Uuid);
+
+impl Default for VariableHandle {
+    /// Same as [`VariableHandle::new`]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Uuid> for VariableHandle {
+    fn from(id: Uuid) -> Self {
+        Self(id)
+    }
+}
+
+impl VariableHandle {
+    /// Construct a handle with a random key
+    #[must_use]
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    /// Underlying key
+    #[must_use]
+    pub fn id(&self) -> Uuid {
+        self.0
+    }
+
+    /// Create an independent variable keyed by this handle
+    pub fn variable<V: Value>(&self, value: V) -> DualNumber<V> {
+        value.into_variable_with_id(self.0)
+    }
+
+    /// Partial derivative w.r.t. the variable of this handle
+    #[must_use]
+    pub fn derivative<V: Value>(&self, dual_number: &DualNumber<V>) -> V {
+        dual_number
+            .dual()
+            .as_ref()
+            .get(&self.0)
+            .copied()
+            .unwrap_or_else(V::zero)
+    }
+}
+
+impl VariableSpace<Uuid> {
+    /// Create an independent variable with a random key and register it
+    ///```
+    /// use autodj::prelude::uuid::*;
+    ///
+    /// let mut space = sparse::VariableSpace::new();
+    /// let x = space.variable(2.0);
+    /// let y = space.variable(3.0);
+    /// let f = x.mul_impl(&y).sub_impl(&y);
+    /// assert_eq!(space.jacobian(&[f, x]), [3.0, 1.0, 1.0, 0.0]);
+    /// ```
+    pub fn variable<V: Value>(&mut self, value: V) -> DualNumber<V> {
+        self.variable_with_key(Uuid::new_v4(), value)
+    }
+
+    /// Create and register independent variables with random keys
+    pub fn variables<V, Values>(&mut self, values: Values) -> Vec<DualNumber<V>>
+    where
+        V: Value,
+        Values: IntoIterator<Item = V>,
+    {
+        values
+            .into_iter()
+            .map(|value| self.variable(value))
+            .collect()
+    }
+}
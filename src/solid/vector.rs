@@ -1,40 +1,283 @@
-//! [`crate::vector::DualNumber`] for dynamic number of variables
+//! [`crate::solid::vector::DualNumber`] for dynamic number of variables
+#![cfg(feature = "alloc")]
 
+pub use crate::error::DimensionMismatch;
 use crate::fluid::{Dual, Value};
 use num_traits::Zero;
-use std::ops::{Add, AddAssign, Mul, MulAssign, Neg};
-use std::prelude::v1::*;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg};
+use std::prelude::v1::{vec, Vec};
 
 /// Newtype wrapper for [`Vec<impl crate::fluid::Value>`].
 /// Should implement [`crate::fluid::Grad`]
 
-#[derive(Clone, Debug, PartialEq, PartialOrd, Default)]
+#[derive(Debug, PartialEq, PartialOrd, Default)]
 pub struct Grad<V: Value>(Vec<V>);
 
+impl<V: Value> Clone for Grad<V> {
+    fn clone(&self) -> Self {
+        #[cfg(feature = "stats")]
+        if !self.0.is_empty() {
+            crate::stats::record_allocation();
+        }
+        Self(self.0.clone())
+    }
+}
+
 impl<V: Value> AsRef<Vec<V>> for Grad<V> {
     fn as_ref(&self) -> &Vec<V> {
         &self.0
     }
 }
 
+impl<V: Value> Index<usize> for Grad<V> {
+    type Output = V;
+
+    #[allow(
+        clippy::indexing_slicing,
+        reason = "out-of-bounds indexing panics by contract"
+    )]
+    fn index(&self, index: usize) -> &V {
+        &self.0[index]
+    }
+}
+
+impl<V: Value> IndexMut<usize> for Grad<V> {
+    #[allow(
+        clippy::indexing_slicing,
+        reason = "out-of-bounds indexing panics by contract"
+    )]
+    fn index_mut(&mut self, index: usize) -> &mut V {
+        &mut self.0[index]
+    }
+}
+
 impl<V: Value, IntoVec: Into<Vec<V>>> From<IntoVec> for Grad<V> {
     fn from(value: IntoVec) -> Self {
         Self(value.into())
     }
 }
 
+impl<V: Value> crate::fluid::GradIter<V> for Grad<V> {
+    type Key = usize;
+
+    fn derivatives(&self) -> impl Iterator<Item = (usize, V)> + '_ {
+        self.0.iter().copied().enumerate()
+    }
+
+    fn dim(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+impl<V: Value> Grad<V> {
+    /// Zero gradient with room for `capacity` derivatives,
+    /// so it grows without reallocations up to that length
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        #[cfg(feature = "stats")]
+        if capacity > 0 {
+            crate::stats::record_allocation();
+        }
+        Self(Vec::with_capacity(capacity))
+    }
+
+    /// Number of derivatives the gradient can hold without reallocating
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Sparse gradient with positions as keys. Zero derivatives are omitted
+    ///```
+    /// use autodj::solid::vector::Grad;
+    ///
+    /// let grad: Grad<f64> = vec![1.0, 0.0, 3.0].into();
+    /// let sparse = grad.to_sparse_indexed();
+    /// assert_eq!(sparse.as_ref().get(&2), Some(&3.0));
+    /// let dense: Grad<f64> = sparse.to_dense(&[0, 1, 2]).into();
+    /// assert_eq!(dense, grad);
+    /// ```
+    #[cfg(feature = "sparse")]
+    #[must_use]
+    pub fn to_sparse_indexed(&self) -> crate::solid::sparse::Grad<usize, V> {
+        let keys: Vec<usize> = (0..self.0.len()).collect();
+        self.to_sparse(&keys)
+    }
+
+    /// Sparse gradient with derivatives paired with given keys. Zero derivatives are omitted,
+    /// as well as excess derivatives or keys
+    ///```
+    /// use autodj::solid::vector::Grad;
+    ///
+    /// let grad: Grad<f64> = vec![1.0, 0.0, 3.0].into();
+    /// let sparse = grad.to_sparse(&["a", "b", "c"]);
+    /// assert_eq!(sparse.to_dense(&["a", "b", "c"]), grad.as_ref().as_slice());
+    /// assert_eq!(sparse.as_ref().len(), 2);
+    /// ```
+    #[cfg(feature = "sparse")]
+    #[must_use]
+    pub fn to_sparse<Key: crate::solid::sparse::GradKey>(
+        &self,
+        keys: &[Key],
+    ) -> crate::solid::sparse::Grad<Key, V> {
+        crate::solid::sparse::from_dense(&self.0, keys)
+    }
+}
+
+impl<V: Value> Grad<V> {
+    /// Check that gradients can be combined without padding.
+    /// Empty gradients (of constants) are compatible with any length
+    ///
+    /// # Errors
+    /// [`DimensionMismatch`] if both gradients are non-empty and their lengths differ
+    pub fn check_len(&self, rhs: &Self) -> Result<(), DimensionMismatch> {
+        let (left, right) = (self.0.len(), rhs.0.len());
+        if left == right || left == 0 || right == 0 {
+            Ok(())
+        } else {
+            Err(DimensionMismatch { left, right })
+        }
+    }
+
+    /// Strict version of [`AddAssign::add_assign`] which does not pad the shorter gradient.
+    /// `self` is left unchanged on error
+    ///
+    /// # Errors
+    /// See [`Grad::check_len`]
+    pub fn try_add_assign(&mut self, rhs: Self) -> Result<(), DimensionMismatch> {
+        self.check_len(&rhs)?;
+        *self += rhs;
+        Ok(())
+    }
+
+    /// Strict version of [`Add::add`] which does not pad the shorter gradient
+    ///
+    /// # Errors
+    /// See [`Grad::check_len`]
+    pub fn try_add(self, rhs: Self) -> Result<Self, DimensionMismatch> {
+        let mut result = self;
+        result.try_add_assign(rhs)?;
+        Ok(result)
+    }
+}
+
 /// For *dynamically*-known number of variables
 pub type DualNumber<V> = crate::solid::DualNumber<V, Grad<V>>;
 
+impl<V: Value, const N: usize> TryFrom<Grad<V>> for crate::solid::array::Grad<V, N> {
+    type Error = DimensionMismatch;
+
+    fn try_from(value: Grad<V>) -> Result<Self, Self::Error> {
+        let right = value.0.len();
+        <[V; N]>::try_from(value.0)
+            .map(Into::into)
+            .map_err(|_| DimensionMismatch { left: N, right })
+    }
+}
+
+impl<V: Value, const N: usize> From<crate::solid::array::DualNumber<V, N>> for DualNumber<V> {
+    fn from(value: crate::solid::array::DualNumber<V, N>) -> Self {
+        let (value, grad) = value.decompose();
+        Self::new(value, Vec::from(grad).into())
+    }
+}
+
+impl<V: Value> From<crate::solid::single::DualNumber<V>> for DualNumber<V> {
+    fn from(value: crate::solid::single::DualNumber<V>) -> Self {
+        let (value, deriv) = value.decompose();
+        Self::new(value, vec![deriv].into())
+    }
+}
+
+/// Conversion into statically-known number of variables
+///```
+/// use autodj::prelude::array::*;
+/// use autodj::solid::vector::{self, DimensionMismatch};
+///
+/// let [x, y] = [1.0, 2.0].into_variables();
+/// let f = x * y;
+///
+/// let dynamic: vector::DualNumber<f64> = f.into();
+/// assert_eq!(dynamic.dual().as_ref(), &vec![2.0, 1.0]);
+///
+/// let fixed: DualNumber<f64, 2> = dynamic.clone().try_into().unwrap();
+/// assert_eq!(fixed, f);
+/// let wrong: Result<DualNumber<f64, 3>, _> = dynamic.try_into();
+/// assert_eq!(wrong, Err(DimensionMismatch { left: 3, right: 2 }));
+/// ```
+impl<V: Value, const N: usize> TryFrom<DualNumber<V>> for crate::solid::array::DualNumber<V, N> {
+    type Error = DimensionMismatch;
+
+    fn try_from(value: DualNumber<V>) -> Result<Self, Self::Error> {
+        let (value, grad) = value.decompose();
+        Ok(Self::new(value, grad.try_into()?))
+    }
+}
+
+impl<V: Value> DualNumber<V> {
+    /// Zero parameter with room for `capacity` derivatives, e.g. an accumulator in a hot loop
+    ///```
+    /// use autodj::prelude::vector::*;
+    ///
+    /// let x: Vec<DualF64> = vec![1.0, 2.0, 3.0].into_variables();
+    /// let mut total = DualF64::with_grad_capacity(x.len());
+    /// for xi in &x {
+    ///     let _ = total.add_assign_impl(xi);
+    /// }
+    /// assert_eq!(total.decompose(), (6.0, vec![1.0, 1.0, 1.0].into()));
+    /// ```
+    #[must_use]
+    pub fn with_grad_capacity(capacity: usize) -> Self {
+        Self::new(V::zero(), Grad::with_capacity(capacity))
+    }
+
+    /// Check that dual numbers come from the same set of variables
+    /// before combining them with arithmetic operations
+    ///```
+    /// use autodj::prelude::vector::*;
+    ///
+    /// let x = vec![1.0, 2.0].into_variables();
+    /// let y = vec![3.0].into_variables();
+    /// assert!(x[0].check_len(&x[1]).is_ok());
+    /// assert_eq!(
+    ///     x[0].check_len(&y[0]),
+    ///     Err(DimensionMismatch { left: 2, right: 1 })
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// See [`Grad::check_len`]
+    pub fn check_len(&self, rhs: &Self) -> Result<(), DimensionMismatch> {
+        self.dual().check_len(rhs.dual())
+    }
+}
+
 impl<V: Value> AddAssign for Grad<V> {
     fn add_assign(&mut self, rhs: Self) {
+        #[cfg(feature = "stats")]
+        if rhs.0.len() > self.0.capacity() {
+            crate::stats::record_allocation();
+        }
         self.0.resize_with(self.0.len().max(rhs.0.len()), V::zero);
-        for (to, from) in self.0.iter_mut().zip(rhs.0.into_iter()) {
+        for (to, from) in self.0.iter_mut().zip(rhs.0) {
             *to += from;
         }
     }
 }
 
+impl<V: Value> crate::fluid::Grad<V> for Grad<V> {
+    fn axpy(&mut self, alpha: V, x: &Self) {
+        #[cfg(feature = "stats")]
+        if x.0.len() > self.0.capacity() {
+            crate::stats::record_allocation();
+        }
+        self.0.resize_with(self.0.len().max(x.0.len()), V::zero);
+        for (to, &from) in self.0.iter_mut().zip(&x.0) {
+            *to += alpha * from;
+        }
+    }
+}
+
 impl<V: Value> MulAssign<V> for Grad<V> {
     fn mul_assign(&mut self, rhs: V) {
         for elem in &mut self.0 {
@@ -57,11 +300,7 @@ impl<V: Value> Neg for Grad<V> {
     type Output = Self;
 
     fn neg(self) -> Self::Output {
-        self.0
-            .into_iter()
-            .map(std::ops::Neg::neg)
-            .collect::<Vec<V>>()
-            .into()
+        self.0.into_iter().map(Neg::neg).collect::<Vec<V>>().into()
     }
 }
 
@@ -98,25 +337,322 @@ pub trait IntoVariables<V: Value>: Into<Vec<V>> {
             let grad_holder = vec![V::zero(); len];
             vec![grad_holder; len]
         };
-        for (index, (mut grad, value)) in grads_holder.into_iter().zip(vec.into_iter()).enumerate()
-        {
+        for (index, (mut grad, value)) in grads_holder.into_iter().zip(vec).enumerate() {
             *grad
                 .get_mut(index)
                 // TODO: consider using `unsafe get_unchecked()` or relax clippy lints
                 .unwrap_or_else(|| {
                     panic!("The index requested here should be valid at this point")
                 }) = V::one();
+            #[cfg(feature = "stats")]
+            crate::stats::record_allocation();
             result.push(Dual::new(value, grad.into()));
         }
         result
     }
+
+    /// Turn [`Vec`] of [`crate::fluid::Value`] into dual variables with given seeds as gradients
+    /// instead of the identity matrix.
+    ///
+    /// Values without a seed become parameters
+    ///```
+    /// use autodj::prelude::vector::*;
+    ///
+    /// // derivative along direction [1, 2]
+    /// let [x, y]: [DualNumber<f64>; 2] = vec![3.0, 4.0]
+    ///     .into_variables_with_seeds([vec![1.0], vec![2.0]])
+    ///     .try_into()
+    ///     .unwrap();
+    /// let f = x.clone() * x + y;
+    /// assert_eq!(f.dual().as_ref(), &vec![8.0]);
+    /// ```
+    fn into_variables_with_seeds<Seeds, Seed>(self, seeds: Seeds) -> Vec<DualNumber<V>>
+    where
+        Seeds: IntoIterator<Item = Seed>,
+        Seed: Into<Grad<V>>,
+    {
+        let vec: Vec<V> = self.into();
+        let mut seeds = seeds.into_iter();
+        vec.into_iter()
+            .map(|value| {
+                let grad = seeds.next().map_or_else(Grad::zero, Into::into);
+                Dual::new(value, grad)
+            })
+            .collect()
+    }
+
+    /// Turn only selected values into independent dual variables, the rest become parameters.
+    ///
+    /// Gradients have a column per selected value in order of appearance.
+    /// Values beyond the mask are parameters
+    ///```
+    /// use autodj::prelude::vector::*;
+    ///
+    /// let [x, p, y]: [DualNumber<f64>; 3] = vec![1.0, 2.0, 3.0]
+    ///     .into_variables_masked(&[true, false, true])
+    ///     .try_into()
+    ///     .unwrap();
+    /// assert_eq!(x.dual().as_ref(), &vec![1.0, 0.0]);
+    /// assert_eq!(p.dual().as_ref(), &vec![0.0, 0.0]);
+    /// assert_eq!(y.dual().as_ref(), &vec![0.0, 1.0]);
+    /// ```
+    fn into_variables_masked(self, mask: &[bool]) -> Vec<DualNumber<V>> {
+        let vec: Vec<V> = self.into();
+        let selected = |index: usize| mask.get(index).copied().unwrap_or(false);
+        let columns = (0..vec.len()).filter(|&index| selected(index)).count();
+        let mut column = 0;
+        vec.into_iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let mut grad = vec![V::zero(); columns];
+                if selected(index) {
+                    if let Some(deriv) = grad.get_mut(column) {
+                        *deriv = V::one();
+                    }
+                    column += 1;
+                }
+                Dual::new(value, grad.into())
+            })
+            .collect()
+    }
 }
 impl<V: Value, IntoVec> IntoVariables<V> for IntoVec where IntoVec: Into<Vec<V>> {}
 
-// TODO: implement in other similar places
+/// Growable set of independent variables.
+///
+/// New variables get next gradient indices,
+/// and gradients of existing variables are extended with zeros,
+/// so indices of already created variables stay the same
+///```
+/// use autodj::prelude::vector::*;
+///
+/// let mut variables: DualVariables<f64> = vec![1.0, 2.0].into();
+/// let index = variables.push(3.0);
+/// assert_eq!(index, 2);
+/// variables.extend([4.0]);
+///
+/// let x = variables.as_ref();
+/// assert_eq!(x[0].dual().as_ref(), &vec![1.0, 0.0, 0.0, 0.0]);
+/// assert_eq!(x[3].dual().as_ref(), &vec![0.0, 0.0, 0.0, 1.0]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DualVariables<V: Value>(Vec<DualNumber<V>>);
+
+impl<V: Value> DualVariables<V> {
+    /// Construct an empty set
+    #[must_use]
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Number of variables
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there are no variables
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Append an independent variable and return its gradient index
+    pub fn push(&mut self, value: V) -> usize {
+        let index = self.0.len();
+        self.extend([value]);
+        index
+    }
+
+    /// Take the variables out of the set
+    #[must_use]
+    pub fn into_inner(self) -> Vec<DualNumber<V>> {
+        self.0
+    }
+
+    /// Evaluate the first stage of a model over the variables
+    pub fn eval<Output, F>(&self, func: F) -> Output
+    where
+        F: FnOnce(&[DualNumber<V>]) -> Output,
+    {
+        func(&self.0)
+    }
+
+    /// Combine two sets into one with non-overlapping gradient indices.
+    /// Variables of `self` keep their indices, variables of `other` follow them
+    ///```
+    /// use autodj::prelude::vector::*;
+    ///
+    /// let x: DualVariables<f64> = vec![1.0].into();
+    /// let y: DualVariables<f64> = vec![2.0, 3.0].into();
+    /// let xy = x.concat(y);
+    /// assert_eq!(xy.len(), 3);
+    /// assert_eq!(xy.as_ref()[0].dual().as_ref(), &vec![1.0, 0.0, 0.0]);
+    /// assert_eq!(xy.as_ref()[1].dual().as_ref(), &vec![0.0, 1.0, 0.0]);
+    /// ```
+    #[must_use]
+    pub fn concat(self, other: Self) -> Self {
+        let mut result = self;
+        result.extend(other.0.into_iter().map(|variable| variable.decompose().0));
+        result
+    }
+
+    /// Overwrite values of the variables keeping their gradients, e.g. between Newton iterations
+    ///```
+    /// use autodj::prelude::vector::*;
+    ///
+    /// let mut variables: DualVariables<f64> = vec![1.0, 2.0].into();
+    /// variables.set_values(&[3.0, 4.0]).unwrap();
+    /// assert_eq!(variables.as_ref()[1].value(), &4.0);
+    /// assert_eq!(variables.as_ref()[1].dual().as_ref(), &vec![0.0, 1.0]);
+    /// ```
+    ///
+    /// # Errors
+    /// [`DimensionMismatch`] if the number of values differs from the number of variables.
+    /// In this case, the variables are left unchanged
+    pub fn set_values(&mut self, values: &[V]) -> Result<(), DimensionMismatch> {
+        if values.len() != self.0.len() {
+            return Err(DimensionMismatch {
+                left: self.0.len(),
+                right: values.len(),
+            });
+        }
+        for (variable, &value) in self.0.iter_mut().zip(values) {
+            *variable.value_mut() = value;
+        }
+        Ok(())
+    }
+}
+
+impl<V: Value> Extend<V> for DualVariables<V> {
+    fn extend<T: IntoIterator<Item = V>>(&mut self, iter: T) {
+        let first = self.0.len();
+        self.0.extend(
+            iter.into_iter()
+                .map(|value| DualNumber::new(value, Grad::zero())),
+        );
+        let len = self.0.len();
+        for (index, variable) in self.0.iter_mut().enumerate() {
+            let grad = &mut variable.dual_mut().0;
+            grad.resize(len, V::zero());
+            if index >= first {
+                if let Some(deriv) = grad.get_mut(index) {
+                    *deriv = V::one();
+                }
+            }
+        }
+    }
+}
+
+impl<V: Value> FromIterator<V> for DualVariables<V> {
+    fn from_iter<T: IntoIterator<Item = V>>(iter: T) -> Self {
+        let mut variables = Self::new();
+        variables.extend(iter);
+        variables
+    }
+}
+
+impl<V: Value, IntoVec: Into<Vec<V>>> From<IntoVec> for DualVariables<V> {
+    fn from(value: IntoVec) -> Self {
+        value.into().into_iter().collect()
+    }
+}
+
+impl<V: Value> AsRef<[DualNumber<V>]> for DualVariables<V> {
+    fn as_ref(&self) -> &[DualNumber<V>] {
+        &self.0
+    }
+}
+
+/// Pool of gradient buffers to recycle across repeated evaluations
+///```
+/// use autodj::prelude::vector::*;
+///
+/// let mut workspace = Workspace::new();
+/// for step in 0..3 {
+///     let shift = f64::from(step);
+///     let f = workspace.eval(&[1.0 + shift, 2.0], |x| x[0].mul_impl(&x[1]));
+///     assert_eq!(f.dual().as_ref(), &vec![2.0, 1.0 + shift]);
+/// }
+/// assert_eq!(workspace.pooled(), 2);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Workspace<V: Value> {
+    /// spare gradient storage
+    buffers: Vec<Vec<V>>,
+    /// storage for independent variables of [`Workspace::eval`]
+    variables: Vec<DualNumber<V>>,
+}
+
+impl<V: Value> Workspace<V> {
+    /// Construct an empty workspace
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            buffers: Vec::new(),
+            variables: Vec::new(),
+        }
+    }
+
+    /// Number of spare gradient buffers
+    #[must_use]
+    pub fn pooled(&self) -> usize {
+        self.buffers.len()
+    }
+
+    /// Zero gradient of given length taken from the pool if possible
+    pub fn zeros(&mut self, len: usize) -> Grad<V> {
+        let mut buffer = self.buffers.pop().unwrap_or_default();
+        #[cfg(feature = "stats")]
+        if len > buffer.capacity() {
+            crate::stats::record_allocation();
+        }
+        buffer.clear();
+        buffer.resize(len, V::zero());
+        Grad(buffer)
+    }
+
+    /// Return gradient storage of a dual number to the pool
+    pub fn recycle(&mut self, dual_number: DualNumber<V>) {
+        let (_, grad) = dual_number.decompose();
+        self.buffers.push(grad.0);
+    }
+
+    /// Return gradient storage of several dual numbers to the pool
+    pub fn recycle_all<Duals: IntoIterator<Item = DualNumber<V>>>(&mut self, dual_numbers: Duals) {
+        for dual_number in dual_numbers {
+            self.recycle(dual_number);
+        }
+    }
+
+    /// Evaluate a function over independent variables built from pooled storage.
+    /// Storage of the variables returns to the pool afterwards,
+    /// while storage of the output can be returned with [`Workspace::recycle`]
+    pub fn eval<Output, Func>(&mut self, values: &[V], func: Func) -> Output
+    where
+        Func: FnOnce(&[DualNumber<V>]) -> Output,
+    {
+        let mut variables = std::mem::take(&mut self.variables);
+        for (index, &value) in values.iter().enumerate() {
+            let mut grad = self.zeros(values.len());
+            if let Some(deriv) = grad.0.get_mut(index) {
+                *deriv = V::one();
+            }
+            variables.push(DualNumber::new(value, grad));
+        }
+        let output = func(&variables);
+        self.recycle_all(variables.drain(..));
+        self.variables = variables;
+        output
+    }
+}
+
 /// Specialization for [`f64`]
 pub type DualF64 = DualNumber<f64>;
 
+/// Specialization for [`f32`]
+pub type DualF32 = DualNumber<f32>;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -142,4 +678,57 @@ mod tests {
         b += a;
         assert_eq!(b, Grad(vec![1.5_f64, 3.0_f64, 3.0_f64]));
     }
+
+    #[test]
+    fn try_add_mismatch() {
+        let a = Grad(vec![1.0_f64, 2.0_f64, 3.0_f64]);
+        let mut b = Grad(vec![0.5_f64, 1.0_f64]);
+        assert_eq!(
+            b.try_add_assign(a),
+            Err(DimensionMismatch { left: 2, right: 3 })
+        );
+        assert_eq!(b, Grad(vec![0.5_f64, 1.0_f64]));
+    }
+
+    #[test]
+    fn push_keeps_indices() {
+        let mut variables = DualVariables::new();
+        assert_eq!(variables.push(1.0_f64), 0);
+        assert_eq!(variables.push(2.0_f64), 1);
+        assert_eq!(variables.len(), 2);
+        let expected: Vec<DualNumber<f64>> = vec![1.0_f64, 2.0_f64].into_variables();
+        assert_eq!(variables.into_inner(), expected);
+    }
+
+    #[test]
+    fn masked_beyond_mask() {
+        let variables = vec![1.0_f64, 2.0_f64, 3.0_f64].into_variables_masked(&[false, true]);
+        let grads: Vec<_> = variables.iter().map(|x| x.dual().clone()).collect();
+        assert_eq!(
+            grads,
+            vec![
+                Grad(vec![0.0_f64]),
+                Grad(vec![1.0_f64]),
+                Grad(vec![0.0_f64])
+            ]
+        );
+    }
+
+    #[test]
+    fn workspace_reuses_buffers() {
+        let mut workspace = Workspace::new();
+        let first = workspace.eval(&[1.0_f64, 2.0_f64], |x| x.first().cloned());
+        assert_eq!(workspace.pooled(), 2);
+        let grad = workspace.zeros(3);
+        assert_eq!(grad, Grad(vec![0.0_f64; 3]));
+        assert_eq!(workspace.pooled(), 1);
+        workspace.recycle_all(first);
+        assert_eq!(workspace.pooled(), 2);
+    }
+
+    #[test]
+    fn try_add_constant() {
+        let a = Grad(vec![1.0_f64, 2.0_f64]);
+        assert_eq!(a.clone().try_add(Grad::zero()), Ok(a));
+    }
 }
@@ -0,0 +1,143 @@
+//! Iterative solvers driven by automatic derivatives
+//!
+//! - [`newton`]: Newton-Raphson iterations for square nonlinear systems
+//! - [`gauss_newton`]: damped Gauss-Newton iterations for least-squares problems
+//! - `roots`: adapters for the `roots` crate (feature `roots`)
+//! - [`trust_region`]: trust-region iterations with dogleg steps for least-squares problems
+#![cfg(feature = "alloc")]
+
+use crate::{
+    error::Error,
+    fluid::{Dual, Value},
+    function::VectorDualFn,
+    solid::vector::{DualNumber, IntoVariables},
+};
+use std::{
+    fmt::{Display, Formatter, Result},
+    prelude::v1::Vec,
+};
+
+pub mod gauss_newton;
+pub mod newton;
+pub mod roots;
+pub mod trust_region;
+
+/// Evaluate residual values and dense Jacobian rows of `columns` length
+pub(crate) fn evaluate<V, F>(residual: &F, point: &[V], columns: usize) -> (Vec<V>, Vec<Vec<V>>)
+where
+    V: Value,
+    F: VectorDualFn<DualNumber<V>> + ?Sized,
+{
+    residual
+        .eval(&point.to_vec().into_variables())
+        .into_iter()
+        .map(|equation| {
+            let (value, grad) = equation.decompose();
+            let mut row = grad.as_ref().clone();
+            row.resize(columns, V::zero());
+            (value, row)
+        })
+        .unzip()
+}
+
+/// Reason of stopping iterations
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Termination {
+    /// tolerance has been reached
+    Converged,
+    /// iteration limit has been exhausted
+    MaxIterations,
+    /// Jacobian could not be inverted (singular, non-square or not finite)
+    SingularJacobian,
+}
+
+impl Display for Termination {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let description = match self {
+            Self::Converged => "converged",
+            Self::MaxIterations => "reached iteration limit",
+            Self::SingularJacobian => "singular Jacobian",
+        };
+        write!(f, "{description}")
+    }
+}
+
+/// Outcome of iterations
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report<V: Value> {
+    /// last iterate
+    solution: Vec<V>,
+    /// Euclidean norm of residuals at the last iterate
+    residual_norm: V,
+    /// number of performed iterations
+    iterations: usize,
+    /// reason of stopping
+    termination: Termination,
+}
+
+impl<V: Value> Report<V> {
+    /// Last iterate
+    #[must_use]
+    pub fn solution(&self) -> &[V] {
+        &self.solution
+    }
+
+    /// Take the last iterate out of the report
+    #[must_use]
+    pub fn into_solution(self) -> Vec<V> {
+        self.solution
+    }
+
+    /// Euclidean norm of residuals at the last iterate
+    #[must_use]
+    pub fn residual_norm(&self) -> V {
+        self.residual_norm
+    }
+
+    /// Number of performed iterations
+    #[must_use]
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Reason of stopping
+    #[must_use]
+    pub fn termination(&self) -> Termination {
+        self.termination
+    }
+
+    /// Whether tolerance has been reached
+    #[must_use]
+    pub fn converged(&self) -> bool {
+        self.termination == Termination::Converged
+    }
+
+    /// Take the last iterate out of the report if converged
+    ///```
+    /// use autodj::prelude::vector::*;
+    /// use autodj::{error::Error, solver::newton::Newton};
+    ///
+    /// let squares = |x: &[DualF64]| x.iter().map(|xi| xi.mul_impl(xi) + 1.0).collect();
+    /// assert_eq!(Newton::new().solve(&squares, &[0.0]).into_result(), Err(Error::Singular));
+    /// ```
+    /// # Errors
+    /// [`Error::Convergence`] or [`Error::Singular`] according to [`Report::termination`]
+    pub fn into_result(self) -> core::result::Result<Vec<V>, Error> {
+        match self.termination {
+            Termination::Converged => Ok(self.solution),
+            Termination::MaxIterations => Err(Error::Convergence),
+            Termination::SingularJacobian => Err(Error::Singular),
+        }
+    }
+}
+
+impl<V: Value + Display> Display for Report<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "{} after {} iterations, |r| = {}",
+            self.termination, self.iterations, self.residual_norm
+        )
+    }
+}
@@ -0,0 +1,146 @@
+//! [`GaussNewton`] solver for (overdetermined) least-squares problems `min |residual(x)|²`
+//!
+//! Jacobians are assembled from dual numbers,
+//! and every step solves damped normal equations `(JᵀJ + damping·I) step = Jᵀr`.
+//!
+//!```
+//! use autodj::prelude::vector::*;
+//! use autodj::solver::gauss_newton::GaussNewton;
+//!
+//! // fit y = a * exp(b * t)
+//! let data = [(0.0, 2.0), (1.0, 2.0 * 0.5_f64.exp()), (2.0, 2.0 * 1.0_f64.exp())];
+//! let residual = |p: &[DualF64]| {
+//!     data.iter()
+//!         .map(|&(t, y)| p[0].mul_impl(&p[1].mul_impl(&t.into()).exp()) - y)
+//!         .collect::<Vec<_>>()
+//! };
+//!
+//! let report = GaussNewton::new().solve(&residual, &[1.0, 0.0]);
+//! assert!(report.converged());
+//! let [a, b]: [f64; 2] = report.solution().try_into().unwrap();
+//! assert!((a - 2.0).abs() < 1e-9);
+//! assert!((b - 0.5).abs() < 1e-9);
+//! ```
+
+use crate::{
+    fluid::Value,
+    function::VectorDualFn,
+    linalg,
+    solid::vector::DualNumber,
+    solver::{evaluate, Report, Termination},
+};
+
+/// Configurable damped Gauss-Newton iterations
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GaussNewton<V: Value> {
+    /// maximum number of iterations
+    max_iterations: usize,
+    /// threshold for both residual norm and step norm
+    tolerance: V,
+    /// Levenberg damping of normal equations
+    damping: V,
+}
+
+impl<V: Value> Default for GaussNewton<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Value> GaussNewton<V> {
+    /// Construct with default settings and no damping
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            max_iterations: 100,
+            tolerance: V::epsilon().sqrt(),
+            damping: V::zero(),
+        }
+    }
+
+    /// Set maximum number of iterations
+    #[must_use]
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Set convergence threshold for residual and step norms
+    #[must_use]
+    pub fn tolerance(mut self, tolerance: V) -> Self {
+        self.tolerance = tolerance.abs();
+        self
+    }
+
+    /// Set Levenberg damping of normal equations
+    #[must_use]
+    pub fn damping(mut self, damping: V) -> Self {
+        self.damping = damping.abs();
+        self
+    }
+
+    /// Minimize the sum of squared residuals over [`DualNumber`]
+    pub fn solve<F>(&self, residual: &F, initial: &[V]) -> Report<V>
+    where
+        F: VectorDualFn<DualNumber<V>> + ?Sized,
+    {
+        let columns = initial.len();
+        let mut solution = initial.to_vec();
+        let (mut values, mut jacobian) = evaluate(residual, &solution, columns);
+        let mut iterations = 0;
+        let mut termination = if linalg::norm(&values) <= self.tolerance {
+            Termination::Converged
+        } else {
+            Termination::MaxIterations
+        };
+
+        while termination != Termination::Converged && iterations < self.max_iterations {
+            let Some(step) = linalg::least_squares(&jacobian, &values, columns, self.damping)
+            else {
+                termination = Termination::SingularJacobian;
+                break;
+            };
+            for (coordinate, &increment) in solution.iter_mut().zip(&step) {
+                *coordinate -= increment;
+            }
+            iterations += 1;
+            (values, jacobian) = evaluate(residual, &solution, columns);
+            if linalg::norm(&values) <= self.tolerance || linalg::norm(&step) <= self.tolerance {
+                termination = Termination::Converged;
+            }
+        }
+
+        Report {
+            residual_norm: linalg::norm(&values),
+            solution,
+            iterations,
+            termination,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fluid::Dual;
+    use std::prelude::v1::Vec;
+
+    #[test]
+    fn rank_deficient_needs_damping() {
+        // both parameters enter as a sum only
+        let residual = |p: &[DualNumber<f64>]| {
+            p.first()
+                .zip(p.get(1))
+                .map(|(a, b)| a.add_impl(b) - 1.0_f64)
+                .into_iter()
+                .collect::<Vec<_>>()
+        };
+        let undamped = GaussNewton::new().solve(&residual, &[0.0_f64, 0.0_f64]);
+        assert_eq!(undamped.termination(), Termination::SingularJacobian);
+
+        let damped = GaussNewton::new()
+            .damping(1e-6_f64)
+            .solve(&residual, &[0.0_f64, 0.0_f64]);
+        assert!(damped.converged());
+    }
+}
@@ -0,0 +1,236 @@
+//! [`Newton`] solver for square nonlinear systems `residual(x) = 0`
+//!
+//! Jacobians are assembled from dual numbers
+//! and linear systems are solved by the built-in Gaussian elimination.
+//! [`Newton::solve_parametric`] also differentiates the solution with respect to parameters.
+//!
+//!```
+//! use autodj::prelude::array::*;
+//! use autodj::solver::{newton::Newton, Termination};
+//!
+//! // intersection of the unit circle with the line y = x
+//! let report = Newton::new().solve_array(
+//!     |[x, y]: [DualNumber<f64, 2>; 2]| [x * x + y * y - 1.0, x - y],
+//!     [1.0, 0.0],
+//! );
+//!
+//! assert_eq!(report.termination(), Termination::Converged);
+//! let [x, y]: [f64; 2] = report.solution().try_into().unwrap();
+//! assert!((x - 0.5_f64.sqrt()).abs() < 1e-12);
+//! assert!((x - y).abs() < 1e-12);
+//! ```
+
+use crate::{
+    fluid::{Dual, Value},
+    function::VectorDualFn,
+    linalg,
+    solid::{array, vector},
+    solver::{evaluate, Report, Termination},
+};
+use std::prelude::v1::Vec;
+
+/// Configurable Newton-Raphson iterations
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Newton<V: Value> {
+    /// maximum number of iterations
+    max_iterations: usize,
+    /// threshold for both residual norm and step norm
+    tolerance: V,
+}
+
+impl<V: Value> Default for Newton<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Value> Newton<V> {
+    /// Construct with default settings
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            max_iterations: 100,
+            tolerance: V::epsilon().sqrt(),
+        }
+    }
+
+    /// Set maximum number of iterations
+    #[must_use]
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Set convergence threshold for residual and step norms
+    #[must_use]
+    pub fn tolerance(mut self, tolerance: V) -> Self {
+        self.tolerance = tolerance.abs();
+        self
+    }
+
+    /// Solve a system over [`vector::DualNumber`]
+    pub fn solve<F>(&self, residual: &F, initial: &[V]) -> Report<V>
+    where
+        F: VectorDualFn<vector::DualNumber<V>> + ?Sized,
+    {
+        let columns = initial.len();
+        self.iterate(|point| evaluate(residual, point, columns), initial.to_vec())
+    }
+
+    /// Solve a system over [`array::DualNumber`]
+    pub fn solve_array<F, const N: usize>(&self, residual: F, initial: [V; N]) -> Report<V>
+    where
+        F: Fn([array::DualNumber<V, N>; N]) -> [array::DualNumber<V, N>; N],
+    {
+        use array::IntoVariables;
+        self.iterate(
+            |point| {
+                let mut values = [V::zero(); N];
+                for (value, &coordinate) in values.iter_mut().zip(point) {
+                    *value = coordinate;
+                }
+                residual(values.into_variables())
+                    .into_iter()
+                    .map(|equation| {
+                        let (value, grad) = equation.decompose();
+                        (value, grad.into())
+                    })
+                    .unzip()
+            },
+            initial.to_vec(),
+        )
+    }
+
+    /// Solve a parametric system `residual(x, p) = 0` over [`vector::DualNumber`]
+    /// together with sensitivities of its solution to parameters.
+    ///
+    /// By the implicit function theorem `∂x*/∂p = -(∂r/∂x)⁻¹ ∂r/∂p`:
+    /// the residual is evaluated once more at the solution with both `x` and `p` seeded,
+    /// and a single elimination of `∂r/∂x` serves all parameters.
+    /// Sensitivities are given by rows of `∂x*ᵢ/∂pⱼ`
+    /// and are [`None`] unless converged with a regular Jacobian
+    pub fn solve_parametric<F>(
+        &self,
+        residual: &F,
+        initial: &[V],
+        parameters: &[V],
+    ) -> (Report<V>, Option<Vec<Vec<V>>>)
+    where
+        F: Fn(&[vector::DualNumber<V>], &[vector::DualNumber<V>]) -> Vec<vector::DualNumber<V>>
+            + ?Sized,
+    {
+        let constants: Vec<_> = parameters
+            .iter()
+            .map(|&parameter| vector::DualNumber::parameter(parameter))
+            .collect();
+        let report = self.solve(
+            &|point: &[vector::DualNumber<V>]| residual(point, &constants),
+            initial,
+        );
+        if !report.converged() {
+            return (report, None);
+        }
+
+        let size = initial.len();
+        let combined = [report.solution(), parameters].concat();
+        let (_, jacobian) = evaluate(
+            &|variables: &[vector::DualNumber<V>]| {
+                let (point, parameters) = variables.split_at(size);
+                residual(point, parameters)
+            },
+            &combined,
+            combined.len(),
+        );
+        let (state, parametric): (Vec<_>, Vec<_>) = jacobian
+            .into_iter()
+            .map(|mut row| {
+                let parametric = row.split_off(size);
+                (row, parametric.into_iter().map(|deriv| -deriv).collect())
+            })
+            .unzip();
+        (report, linalg::solve_many(state, parametric))
+    }
+
+    /// Newton iterations over residual values and Jacobian rows
+    fn iterate<Eval>(&self, evaluate: Eval, initial: Vec<V>) -> Report<V>
+    where
+        Eval: Fn(&[V]) -> (Vec<V>, Vec<Vec<V>>),
+    {
+        let mut solution = initial;
+        let (mut values, mut jacobian) = evaluate(&solution);
+        let mut iterations = 0;
+        let mut termination = if linalg::norm(&values) <= self.tolerance {
+            Termination::Converged
+        } else {
+            Termination::MaxIterations
+        };
+
+        while termination != Termination::Converged && iterations < self.max_iterations {
+            let Some(step) = linalg::solve(jacobian, values.clone()) else {
+                termination = Termination::SingularJacobian;
+                break;
+            };
+            for (coordinate, &increment) in solution.iter_mut().zip(&step) {
+                *coordinate -= increment;
+            }
+            iterations += 1;
+            (values, jacobian) = evaluate(&solution);
+            if linalg::norm(&values) <= self.tolerance || linalg::norm(&step) <= self.tolerance {
+                termination = Termination::Converged;
+            }
+        }
+
+        Report {
+            residual_norm: linalg::norm(&values),
+            solution,
+            iterations,
+            termination,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::prelude::v1::vec;
+
+    #[test]
+    fn singular_jacobian() {
+        let report = Newton::new().solve(
+            &|x: &[vector::DualNumber<f64>]| x.iter().map(|x| x.mul_impl(x) + 1.0_f64).collect(),
+            &[0.0_f64],
+        );
+        assert_eq!(report.termination(), Termination::SingularJacobian);
+        assert_eq!(report.iterations(), 0);
+        assert_eq!(report.into_result(), Err(crate::error::Error::Singular));
+    }
+
+    #[test]
+    fn square_root_sensitivity() {
+        // x² = p has the solution x = √p with dx/dp = 1 / (2√p)
+        let (report, sensitivities) = Newton::new().solve_parametric(
+            &|x: &[vector::DualNumber<f64>], p: &[vector::DualNumber<f64>]| {
+                x.iter()
+                    .zip(p)
+                    .map(|(x, p)| x.mul_impl(x) - p.clone())
+                    .collect()
+            },
+            &[1.0_f64],
+            &[4.0_f64],
+        );
+        assert!(report.converged());
+        let sensitivity = sensitivities
+            .as_deref()
+            .and_then(<[_]>::first)
+            .and_then(|row| row.first())
+            .copied();
+        assert!(sensitivity.is_some_and(|sensitivity| (sensitivity - 0.25_f64).abs() < 1e-9_f64));
+    }
+
+    #[test]
+    fn already_converged() {
+        let report = Newton::new().solve(&|x: &[vector::DualNumber<f64>]| x.to_vec(), &[0.0_f64]);
+        assert!(report.converged());
+        assert_eq!(report.into_result(), Ok(vec![0.0_f64]));
+    }
+}
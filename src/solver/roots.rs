@@ -0,0 +1,79 @@
+//! Interoperability with the [`roots`] crate
+//!
+//! A scalar function over [`DualNumber`] provides both `f(x)` and `f′(x)`
+//! in the shape that derivative-based searches of [`roots`] expect
+//!
+//!```
+//! use autodj::prelude::single::*;
+//! use autodj::solver::roots::find_root_newton_raphson;
+//!
+//! let root = find_root_newton_raphson(10.0, &|x: DualF64| x * x - 1.0, &mut 1e-15);
+//! assert!((root.unwrap() - 1.0).abs() < 1e-12);
+//! ```
+#![cfg(feature = "roots")]
+
+use crate::{
+    fluid::{Dual, Value},
+    function::ScalarDualFn,
+    solid::single::{DualNumber, IntoVariable},
+};
+use ::roots::{Convergency, FloatType, SearchError};
+
+/// Split a function over [`DualNumber`] into a pair of `f(x)` and `f′(x)` closures
+/// accepted by [`roots::find_root_newton_raphson`]
+pub fn value_and_derivative<V, F>(function: &F) -> (impl Fn(V) -> V + '_, impl Fn(V) -> V + '_)
+where
+    V: Value,
+    F: ScalarDualFn<DualNumber<V>> + ?Sized,
+{
+    (
+        move |x: V| *function.eval(x.into_variable()).value(),
+        move |x: V| *function.eval(x.into_variable()).dual(),
+    )
+}
+
+/// [`roots::find_root_newton_raphson`]
+/// with the derivative computed automatically
+///
+/// # Errors
+/// Same as of [`roots::find_root_newton_raphson`]
+pub fn find_root_newton_raphson<V, F>(
+    start: V,
+    function: &F,
+    convergency: &mut dyn Convergency<V>,
+) -> Result<V, SearchError>
+where
+    V: Value + FloatType,
+    F: ScalarDualFn<DualNumber<V>> + ?Sized,
+{
+    let (value, derivative) = value_and_derivative(function);
+    ::roots::find_root_newton_raphson(start, value, derivative, convergency)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::roots::SimpleConvergency;
+
+    #[test]
+    fn cubic_root() {
+        let mut convergency = SimpleConvergency {
+            eps: 1e-12_f64,
+            max_iter: 50,
+        };
+        let root = find_root_newton_raphson(
+            1.0_f64,
+            &|x: DualNumber<f64>| x.powf(3.0_f64) - 8.0_f64,
+            &mut convergency,
+        );
+        assert!(root.is_ok_and(|root| (root - 2.0_f64).abs() < 1e-9_f64));
+    }
+
+    #[test]
+    fn pair_matches_dual() {
+        let function = |x: DualNumber<f64>| x.sin();
+        let (value, derivative) = value_and_derivative(&function);
+        assert!((value(1.0_f64) - 1.0_f64.sin()).abs() < f64::EPSILON);
+        assert!((derivative(1.0_f64) - 1.0_f64.cos()).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,258 @@
+//! Trust-region iterations with [`dogleg`] steps for least-squares problems `min |residual(x)|²`
+//!
+//! The quadratic model `m(p) = gᵀp + ½ pᵀBp` uses the gradient `g = Jᵀr`
+//! and the Gauss-Newton Hessian approximation `B = JᵀJ`, both assembled from dual numbers.
+//! Steps are confined to a radius that adapts to the agreement of the model with actual reduction,
+//! which keeps iterations stable where pure Newton steps overshoot.
+//!
+//!```
+//! use autodj::prelude::vector::*;
+//! use autodj::solver::trust_region::TrustRegion;
+//!
+//! // stiff system with a root at (1, 1)
+//! let residual = |x: &[DualF64]| {
+//!     vec![
+//!         (x[1].clone() - x[0].mul_impl(&x[0])) * 100.0,
+//!         DualF64::parameter(1.0) - x[0].clone(),
+//!     ]
+//! };
+//!
+//! let report = TrustRegion::new().solve(&residual, &[-1.2, 1.0]);
+//! assert!(report.converged());
+//! assert!((report.solution()[0] - 1.0).abs() < 1e-9);
+//! assert!((report.solution()[1] - 1.0).abs() < 1e-9);
+//! ```
+
+use crate::{
+    fluid::{half, Value},
+    function::VectorDualFn,
+    linalg,
+    solid::vector::DualNumber,
+    solver::{evaluate, Report, Termination},
+};
+use std::prelude::v1::{vec, Vec};
+
+/// Dogleg minimizer of the model `gᵀp + ½ pᵀBp` subject to `|p| ≤ radius`.
+///
+/// Takes the full step `-B⁻¹g` if it fits into the radius,
+/// otherwise follows the path from the Cauchy point towards it up to the boundary.
+/// Falls back to steepest descent if `B` is singular or not positive along `g`.
+/// `hessian` is given by rows
+#[must_use]
+pub fn dogleg<V: Value>(gradient: &[V], hessian: &[Vec<V>], radius: V) -> Vec<V> {
+    let gradient_norm = linalg::norm(gradient);
+    if gradient_norm.is_zero() {
+        return vec![V::zero(); gradient.len()];
+    }
+    let boundary = |direction: &[V], norm: V| -> Vec<V> {
+        direction.iter().map(|&elem| elem * radius / norm).collect()
+    };
+    let descent: Vec<V> = gradient.iter().map(|&deriv| -deriv).collect();
+    let curvature = linalg::dot(gradient, &linalg::mul_vec(hessian, gradient));
+    if curvature <= V::zero() {
+        return boundary(&descent, gradient_norm);
+    }
+
+    let cauchy: Vec<V> = descent
+        .iter()
+        .map(|&elem| elem * gradient_norm * gradient_norm / curvature)
+        .collect();
+    let cauchy_norm = linalg::norm(&cauchy);
+    if cauchy_norm >= radius {
+        return boundary(&descent, gradient_norm);
+    }
+    let Some(full) = linalg::solve(hessian.to_vec(), descent) else {
+        return cauchy;
+    };
+    if linalg::norm(&full) <= radius {
+        return full;
+    }
+
+    // |cauchy + tau * (full - cauchy)| = radius with tau in [0, 1]
+    let leg: Vec<V> = full
+        .iter()
+        .zip(&cauchy)
+        .map(|(&full, &cauchy)| full - cauchy)
+        .collect();
+    let a = linalg::dot(&leg, &leg);
+    let b = linalg::dot(&cauchy, &leg);
+    let c = cauchy_norm * cauchy_norm - radius * radius;
+    let tau = (-b + (b * b - a * c).sqrt()) / a;
+    cauchy
+        .iter()
+        .zip(&leg)
+        .map(|(&cauchy, &leg)| cauchy + tau * leg)
+        .collect()
+}
+
+/// Configurable trust-region iterations with [`dogleg`] steps
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrustRegion<V: Value> {
+    /// maximum number of iterations, including rejected steps
+    max_iterations: usize,
+    /// threshold for residual, gradient and step norms
+    tolerance: V,
+    /// radius of the first iteration
+    initial_radius: V,
+    /// upper bound of the radius
+    max_radius: V,
+}
+
+impl<V: Value> Default for TrustRegion<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Value> TrustRegion<V> {
+    /// Construct with default settings and unit initial radius
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            max_iterations: 200,
+            tolerance: V::epsilon().sqrt(),
+            initial_radius: V::one(),
+            max_radius: V::max_value(),
+        }
+    }
+
+    /// Set maximum number of iterations, including rejected steps
+    #[must_use]
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Set convergence threshold for residual, gradient and step norms
+    #[must_use]
+    pub fn tolerance(mut self, tolerance: V) -> Self {
+        self.tolerance = tolerance.abs();
+        self
+    }
+
+    /// Set radius of the first iteration
+    #[must_use]
+    pub fn initial_radius(mut self, initial_radius: V) -> Self {
+        self.initial_radius = initial_radius.abs();
+        self
+    }
+
+    /// Set upper bound of the radius
+    #[must_use]
+    pub fn max_radius(mut self, max_radius: V) -> Self {
+        self.max_radius = max_radius.abs();
+        self
+    }
+
+    /// Minimize the sum of squared residuals over [`DualNumber`]
+    pub fn solve<F>(&self, residual: &F, initial: &[V]) -> Report<V>
+    where
+        F: VectorDualFn<DualNumber<V>> + ?Sized,
+    {
+        let columns = initial.len();
+        let quarter = half::<V>() * half();
+        let mut radius = self.initial_radius.min(self.max_radius);
+        let mut solution = initial.to_vec();
+        let (mut values, mut jacobian) = evaluate(residual, &solution, columns);
+        let mut iterations = 0;
+        let mut termination = Termination::MaxIterations;
+
+        while iterations < self.max_iterations {
+            let (hessian, gradient) = linalg::normal_equations(&jacobian, &values, columns);
+            if linalg::norm(&values) <= self.tolerance || linalg::norm(&gradient) <= self.tolerance
+            {
+                termination = Termination::Converged;
+                break;
+            }
+            iterations += 1;
+
+            let step = dogleg(&gradient, &hessian, radius);
+            let step_norm = linalg::norm(&step);
+            let predicted = -(linalg::dot(&gradient, &step)
+                + half::<V>() * linalg::dot(&step, &linalg::mul_vec(&hessian, &step)));
+            let candidate: Vec<V> = solution
+                .iter()
+                .zip(&step)
+                .map(|(&coordinate, &increment)| coordinate + increment)
+                .collect();
+            let (candidate_values, candidate_jacobian) = evaluate(residual, &candidate, columns);
+            let actual = half::<V>()
+                * (linalg::dot(&values, &values)
+                    - linalg::dot(&candidate_values, &candidate_values));
+            let ratio = actual / predicted;
+
+            // poor agreement, including not-a-number, shrinks the radius
+            if ratio >= quarter {
+                if ratio > V::one() - quarter && step_norm >= radius * (V::one() - quarter) {
+                    radius = (radius + radius).min(self.max_radius);
+                }
+            } else {
+                radius = quarter * step_norm;
+            }
+            if ratio > quarter * quarter {
+                (solution, values, jacobian) = (candidate, candidate_values, candidate_jacobian);
+                if step_norm <= self.tolerance {
+                    termination = Termination::Converged;
+                    break;
+                }
+            } else if radius <= V::epsilon() {
+                break;
+            }
+        }
+
+        Report {
+            residual_norm: linalg::norm(&values),
+            solution,
+            iterations,
+            termination,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+    use crate::fluid::Dual;
+
+    #[test]
+    fn dogleg_regimes() {
+        let gradient = [2.0_f64, 0.0_f64];
+        let hessian = vec![vec![1.0_f64, 0.0_f64], vec![0.0_f64, 1.0_f64]];
+        assert_eq!(
+            dogleg(&gradient, &hessian, 10.0_f64),
+            vec![-2.0_f64, 0.0_f64]
+        );
+        assert_eq!(
+            dogleg(&gradient, &hessian, 0.5_f64),
+            vec![-0.5_f64, 0.0_f64]
+        );
+        assert_eq!(
+            dogleg(&[0.0_f64, 0.0_f64], &hessian, 1.0_f64),
+            vec![0.0_f64, 0.0_f64]
+        );
+    }
+
+    #[test]
+    fn dogleg_stays_within_radius() {
+        let gradient = [1.0_f64, 1.0_f64];
+        let hessian = vec![vec![1.0_f64, 0.0_f64], vec![0.0_f64, 100.0_f64]];
+        let step = dogleg(&gradient, &hessian, 0.5_f64);
+        assert!((linalg::norm(&step) - 0.5_f64).abs() < 1e-12_f64);
+        assert!(linalg::dot(&gradient, &step) < 0.0_f64);
+    }
+
+    #[test]
+    fn rank_deficient_without_damping() {
+        // both parameters enter as a sum only
+        let residual = |p: &[DualNumber<f64>]| {
+            p.first()
+                .zip(p.get(1))
+                .map(|(a, b)| a.add_impl(b) - 1.0_f64)
+                .into_iter()
+                .collect::<Vec<_>>()
+        };
+        let report = TrustRegion::new().solve(&residual, &[0.0_f64, 0.0_f64]);
+        assert!(report.converged());
+    }
+}
@@ -0,0 +1,286 @@
+//! Detection of Jacobian sparsity patterns with sparse dual numbers
+//!
+//! A single evaluation with [`crate::solid::sparse::indexed`] variables
+//! reveals which variables every equation depends on.
+//! Dependencies are structural: a derivative which happens to be zero at the evaluation point
+//! is still recorded as long as it has been propagated through arithmetic operations.
+//!
+//!```
+//! use autodj::prelude::indexed::*;
+//! use autodj::sparsity::sparsity_pattern;
+//!
+//! // tridiagonal system
+//! let residual = |x: &[DualNumber<f64>]| {
+//!     (0..x.len())
+//!         .map(|i| {
+//!             let mut equation = x[i].mul_impl(&2.0.into());
+//!             if i > 0 {
+//!                 equation -= x[i - 1].clone();
+//!             }
+//!             if i + 1 < x.len() {
+//!                 equation -= x[i + 1].clone();
+//!             }
+//!             equation
+//!         })
+//!         .collect::<Vec<_>>()
+//! };
+//!
+//! let pattern = sparsity_pattern(&residual, 4);
+//! assert_eq!(pattern.len(), 10);
+//! assert!(pattern.contains(1, 2));
+//! assert!(!pattern.contains(0, 3));
+//! ```
+//!
+//! Structurally orthogonal columns can share a forward sweep,
+//! so a banded Jacobian is recovered with as many sweeps as the band width
+//!
+//!```
+//! # use autodj::prelude::indexed::*;
+//! # use autodj::sparsity::sparsity_pattern;
+//! use autodj::prelude::vector::DualNumber as DenseDual;
+//! use autodj::sparsity::compressed_jacobian;
+//!
+//! let square_neighbours = |x: &[DenseDual<f64>]| {
+//!     (0..x.len())
+//!         .map(|i| {
+//!             let left = if i > 0 { x[i - 1].clone() } else { 0.0.into() };
+//!             left + x[i].clone() * x[i].clone()
+//!         })
+//!         .collect::<Vec<_>>()
+//! };
+//! let pattern = sparsity_pattern(
+//!     &|x: &[DualNumber<f64>]| (0..x.len()).map(|i| {
+//!         let left = if i > 0 { x[i - 1].clone() } else { 0.0.into() };
+//!         left + x[i].mul_impl(&x[i])
+//!     }).collect::<Vec<_>>(),
+//!     5,
+//! );
+//! assert_eq!(pattern.colors(), 2);
+//!
+//! let jacobian = compressed_jacobian(&square_neighbours, &[1.0, 2.0, 3.0, 4.0, 5.0], &pattern);
+//! assert_eq!(jacobian[..5], [2.0, 0.0, 0.0, 0.0, 0.0]);
+//! assert_eq!(jacobian[5..10], [1.0, 4.0, 0.0, 0.0, 0.0]);
+//! assert_eq!(jacobian[20..], [0.0, 0.0, 0.0, 1.0, 10.0]);
+//! ```
+#![cfg(feature = "sparse")]
+
+use crate::{
+    fluid::{Dual, Value},
+    function::VectorDualFn,
+    solid::{
+        sparse::indexed::{DualNumber, VariableFactory},
+        vector,
+    },
+};
+use std::{
+    collections::BTreeSet,
+    prelude::v1::{vec, Vec},
+};
+
+/// Set of `(row, column)` pairs of structurally nonzero Jacobian entries
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SparsityPattern {
+    /// number of equations
+    rows: usize,
+    /// number of variables
+    columns: usize,
+    /// nonzero entries in row-major order
+    entries: BTreeSet<(usize, usize)>,
+}
+
+impl SparsityPattern {
+    /// Construct from dimensions and nonzero entries.
+    ///
+    /// Entries outside of the dimensions are ignored
+    pub fn new<Entries>(rows: usize, columns: usize, entries: Entries) -> Self
+    where
+        Entries: IntoIterator<Item = (usize, usize)>,
+    {
+        let entries = entries
+            .into_iter()
+            .filter(|&(row, column)| row < rows && column < columns)
+            .collect();
+        Self {
+            rows,
+            columns,
+            entries,
+        }
+    }
+
+    /// Number of equations
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Number of variables
+    #[must_use]
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Number of nonzero entries
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether there are no nonzero entries
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether an entry is nonzero
+    #[must_use]
+    pub fn contains(&self, row: usize, column: usize) -> bool {
+        self.entries.contains(&(row, column))
+    }
+
+    /// Nonzero entries in row-major order
+    pub fn entries(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.entries.iter().copied()
+    }
+
+    /// Rows which depend on each column
+    #[must_use]
+    pub fn column_dependencies(&self) -> Vec<Vec<usize>> {
+        let mut dependencies = vec![Vec::new(); self.columns];
+        for &(row, column) in &self.entries {
+            if let Some(rows) = dependencies.get_mut(column) {
+                rows.push(row);
+            }
+        }
+        dependencies
+    }
+
+    /// Greedy coloring of columns such that
+    /// columns sharing a nonzero row have different colors
+    #[must_use]
+    pub fn column_coloring(&self) -> Vec<usize> {
+        let mut row_columns = vec![Vec::new(); self.rows];
+        for &(row, column) in &self.entries {
+            if let Some(columns) = row_columns.get_mut(row) {
+                columns.push(column);
+            }
+        }
+        let mut colors: Vec<Option<usize>> = vec![None; self.columns];
+        for (column, rows) in self.column_dependencies().into_iter().enumerate() {
+            let forbidden: BTreeSet<usize> = rows
+                .iter()
+                .filter_map(|&row| row_columns.get(row))
+                .flatten()
+                .filter_map(|&neighbour| colors.get(neighbour).copied().flatten())
+                .collect();
+            let color = (0..=forbidden.len())
+                .find(|color| !forbidden.contains(color))
+                .unwrap_or_default();
+            if let Some(slot) = colors.get_mut(column) {
+                *slot = Some(color);
+            }
+        }
+        colors.into_iter().map(Option::unwrap_or_default).collect()
+    }
+
+    /// Number of forward sweeps required by [`compressed_jacobian`]
+    #[must_use]
+    pub fn colors(&self) -> usize {
+        self.column_coloring()
+            .into_iter()
+            .max()
+            .map_or(0, |color| color + 1)
+    }
+}
+
+/// Detect the sparsity pattern of a function of `num_variables` variables
+/// by evaluating it at a point of all ones
+pub fn sparsity_pattern<V, F>(func: &F, num_variables: usize) -> SparsityPattern
+where
+    V: Value,
+    F: VectorDualFn<DualNumber<V>> + ?Sized,
+{
+    sparsity_pattern_at(func, &vec![V::one(); num_variables])
+}
+
+/// Detect the sparsity pattern of a function by evaluating it at a given point
+pub fn sparsity_pattern_at<V, F>(func: &F, point: &[V]) -> SparsityPattern
+where
+    V: Value,
+    F: VectorDualFn<DualNumber<V>> + ?Sized,
+{
+    let mut factory = VariableFactory::new();
+    let variables: Vec<_> = point.iter().map(|&value| factory.variable(value)).collect();
+    let equations = func.eval(&variables);
+    let entries = equations
+        .iter()
+        .enumerate()
+        .flat_map(|(row, equation)| {
+            equation
+                .dual()
+                .as_ref()
+                .keys()
+                .map(move |&column| (row, column))
+        })
+        .collect::<Vec<_>>();
+    SparsityPattern::new(equations.len(), point.len(), entries)
+}
+
+/// Dense row-major Jacobian computed with one forward sweep per color of [`SparsityPattern::column_coloring`].
+///
+/// Entries absent from the pattern are zero.
+/// The point is expected to have [`SparsityPattern::columns`] elements
+pub fn compressed_jacobian<V, F>(func: &F, point: &[V], pattern: &SparsityPattern) -> Vec<V>
+where
+    V: Value,
+    F: VectorDualFn<vector::DualNumber<V>> + ?Sized,
+{
+    let coloring = pattern.column_coloring();
+    let colors = pattern.colors();
+    let variables: Vec<_> = point
+        .iter()
+        .enumerate()
+        .map(|(column, &value)| {
+            let mut seed = vec![V::zero(); colors];
+            if let Some(derivative) = coloring.get(column).and_then(|&color| seed.get_mut(color)) {
+                *derivative = V::one();
+            }
+            vector::DualNumber::new(value, seed.into())
+        })
+        .collect();
+    let equations = func.eval(&variables);
+    let mut jacobian = vec![V::zero(); pattern.rows() * pattern.columns()];
+    for (row, column) in pattern.entries() {
+        let derivative = equations
+            .get(row)
+            .zip(coloring.get(column))
+            .and_then(|(equation, &color)| equation.dual().as_ref().get(color));
+        if let (Some(&derivative), Some(entry)) = (
+            derivative,
+            jacobian.get_mut(row * pattern.columns() + column),
+        ) {
+            *entry = derivative;
+        }
+    }
+    jacobian
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dense_pattern_needs_all_colors() {
+        let pattern = SparsityPattern::new(
+            2,
+            3,
+            (0..2).flat_map(|row| (0..3).map(move |column| (row, column))),
+        );
+        assert_eq!(pattern.column_coloring(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn diagonal_pattern_needs_one_color() {
+        let pattern = SparsityPattern::new(4, 4, (0..4).map(|i| (i, i)));
+        assert_eq!(pattern.colors(), 1);
+    }
+}
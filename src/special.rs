@@ -0,0 +1,149 @@
+//! Differentiable special functions backed by [`libm`]
+//!
+//!```
+//! use autodj::prelude::single::*;
+//! use autodj::special::{erf, erfc};
+//!
+//! let x = 0.0.into_variable();
+//! assert_eq!(erf(&x).decompose(), (0.0, core::f64::consts::FRAC_2_SQRT_PI));
+//! assert_eq!(erfc(&x).decompose(), (1.0, -core::f64::consts::FRAC_2_SQRT_PI));
+//! ```
+//!
+//! Standard normal distribution functions are handy for option greeks
+//!
+//!```
+//! use autodj::prelude::single::*;
+//! use autodj::special::{norm_cdf, norm_pdf};
+//!
+//! let d1 = 0.0.into_variable();
+//! let (delta, gamma) = norm_cdf(&d1).decompose();
+//! assert_eq!(delta, 0.5);
+//! assert_eq!(gamma, norm_pdf(&d1).value().to_owned());
+//! ```
+#![cfg(feature = "libm")]
+
+use crate::fluid::{half, Dual, Value};
+
+/// [`Value`] implementors with the error function available
+pub trait Erf: Value {
+    /// `2/√π`
+    const FRAC_2_SQRT_PI: Self;
+
+    /// `1/√2`
+    const FRAC_1_SQRT_2: Self;
+
+    /// Error function
+    #[must_use]
+    fn erf(self) -> Self;
+
+    /// Complementary error function `1 - erf(x)`
+    #[must_use]
+    fn erfc(self) -> Self;
+}
+
+impl Erf for f64 {
+    const FRAC_2_SQRT_PI: Self = core::f64::consts::FRAC_2_SQRT_PI;
+    const FRAC_1_SQRT_2: Self = core::f64::consts::FRAC_1_SQRT_2;
+
+    fn erf(self) -> Self {
+        libm::erf(self)
+    }
+
+    fn erfc(self) -> Self {
+        libm::erfc(self)
+    }
+}
+
+impl Erf for f32 {
+    const FRAC_2_SQRT_PI: Self = core::f32::consts::FRAC_2_SQRT_PI;
+    const FRAC_1_SQRT_2: Self = core::f32::consts::FRAC_1_SQRT_2;
+
+    fn erf(self) -> Self {
+        libm::erff(self)
+    }
+
+    fn erfc(self) -> Self {
+        libm::erfcf(self)
+    }
+}
+
+/// Derivative of [`Erf::erf`]: `2/√π·exp(-x²)`
+fn erf_deriv<V: Erf>(x: V) -> V {
+    V::FRAC_2_SQRT_PI * (-x * x).exp()
+}
+
+/// Differentiable [`Erf::erf`]
+#[must_use]
+pub fn erf<D>(x: &D) -> D
+where
+    D: Dual,
+    D::Value: Erf,
+{
+    x.chain(|&x| (x.erf(), erf_deriv(x)))
+}
+
+/// Differentiable [`Erf::erfc`]
+#[must_use]
+pub fn erfc<D>(x: &D) -> D
+where
+    D: Dual,
+    D::Value: Erf,
+{
+    x.chain(|&x| (x.erfc(), -erf_deriv(x)))
+}
+
+/// Standard normal probability density `exp(-x²/2)/√(2π)`
+fn pdf<V: Erf>(x: V) -> V {
+    V::FRAC_2_SQRT_PI * V::FRAC_1_SQRT_2 * half::<V>() * (-x * x * half::<V>()).exp()
+}
+
+/// Differentiable probability density function of the standard normal distribution
+#[must_use]
+pub fn norm_pdf<D>(x: &D) -> D
+where
+    D: Dual,
+    D::Value: Erf,
+{
+    x.chain(|&x| {
+        let density = pdf(x);
+        (density, -x * density)
+    })
+}
+
+/// Differentiable cumulative distribution function of the standard normal distribution
+#[must_use]
+pub fn norm_cdf<D>(x: &D) -> D
+where
+    D: Dual,
+    D::Value: Erf,
+{
+    x.chain(|&x| {
+        (
+            (-x * D::Value::FRAC_1_SQRT_2).erfc() * half::<D::Value>(),
+            pdf(x),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solid::single::{DualF32, DualF64, IntoVariable};
+
+    #[test]
+    fn norm_cdf_tail() {
+        let (cdf, pdf) = norm_cdf(&1.0_f64.into_variable()).decompose();
+        assert!((cdf - 0.841_344_746_068_542_9_f64).abs() < 1e-15_f64);
+        assert!((pdf - 0.241_970_724_519_143_37_f64).abs() < 1e-15_f64);
+    }
+
+    #[test]
+    fn single_precision() {
+        let x: DualF32 = 1.0_f32.into_variable();
+        let y: DualF64 = 1.0_f64.into_variable();
+        let (erf32, derf32) = erf(&x).decompose();
+        let (erf64, derf64) = erf(&y).decompose();
+        assert!((f64::from(erf32) - erf64).abs() < 1e-6_f64);
+        assert!((f64::from(derf32) - derf64).abs() < 1e-6_f64);
+    }
+}
@@ -0,0 +1,241 @@
+//! Counters of automatic differentiation overhead
+//!
+//! Every thread keeps its own counters of
+//! - primitive dual operations: additions, subtractions, multiplications, divisions and negations,
+//! - [`Dual::chain`](crate::fluid::Dual::chain) invocations, i.e. elementary functions,
+//! - heap allocations of [`vector`](crate::solid::vector) gradients.
+//!
+//! Work done on other threads (e.g. in [`parallel`](crate::parallel)) is not included
+//!
+//!```
+//! use autodj::prelude::single::*;
+//! use autodj::stats::measure;
+//!
+//! let (f, stats) = measure(|| {
+//!     let x = 2.0.into_variable();
+//!     x * x + x.sin()
+//! });
+//! assert_eq!(f.dual(), &(4.0 + 2.0_f64.cos()));
+//! assert_eq!(stats.operations(), 2);
+//! assert_eq!(stats.chains(), 1);
+//! assert_eq!(stats.allocations(), 0);
+//! ```
+#![cfg(feature = "stats")]
+
+use std::{
+    cell::Cell,
+    fmt::{Display, Formatter, Result},
+};
+
+std::thread_local! {
+    /// counters of the current thread
+    static COUNTERS: Cell<Stats> = const { Cell::new(Stats::new()) };
+}
+
+/// Snapshot of counters
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Stats {
+    /// number of primitive dual operations
+    operations: usize,
+    /// number of chain rule applications
+    chains: usize,
+    /// number of gradient allocations
+    allocations: usize,
+}
+
+impl Stats {
+    /// All counters at zero
+    const fn new() -> Self {
+        Self {
+            operations: 0,
+            chains: 0,
+            allocations: 0,
+        }
+    }
+
+    /// Number of primitive dual operations: additions, subtractions, multiplications, divisions and negations
+    #[must_use]
+    pub fn operations(&self) -> usize {
+        self.operations
+    }
+
+    /// Number of [`Dual::chain`](crate::fluid::Dual::chain) invocations
+    #[must_use]
+    pub fn chains(&self) -> usize {
+        self.chains
+    }
+
+    /// Number of heap allocations of gradients
+    #[must_use]
+    pub fn allocations(&self) -> usize {
+        self.allocations
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "{} operations, {} chains, {} allocations",
+            self.operations, self.chains, self.allocations
+        )
+    }
+}
+
+/// Counters of the current thread accumulated since the last [`reset`]
+#[must_use]
+pub fn report() -> Stats {
+    COUNTERS.with(Cell::get)
+}
+
+/// Zero the counters of the current thread
+pub fn reset() {
+    COUNTERS.with(|counters| counters.set(Stats::new()));
+}
+
+/// Evaluate a closure and count its overhead on the current thread.
+/// Counters accumulated before are preserved
+pub fn measure<Output, F: FnOnce() -> Output>(func: F) -> (Output, Stats) {
+    let before = report();
+    reset();
+    let output = func();
+    let during = report();
+    COUNTERS.with(|counters| {
+        counters.set(Stats {
+            operations: before.operations + during.operations,
+            chains: before.chains + during.chains,
+            allocations: before.allocations + during.allocations,
+        });
+    });
+    (output, during)
+}
+
+/// Apply an update to the counters of the current thread
+fn update<F: FnOnce(&mut Stats)>(func: F) {
+    COUNTERS.with(|counters| {
+        let mut stats = counters.get();
+        func(&mut stats);
+        counters.set(stats);
+    });
+}
+
+/// Count a primitive dual operation
+pub(crate) fn record_operation() {
+    update(|stats| stats.operations += 1);
+}
+
+/// Count a chain rule application
+pub(crate) fn record_chain() {
+    update(|stats| stats.chains += 1);
+}
+
+/// Count a gradient allocation
+pub(crate) fn record_allocation() {
+    update(|stats| stats.allocations += 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fluid::Dual,
+        solid::vector::{DualNumber, IntoVariables},
+    };
+    use std::prelude::v1::{vec, Vec};
+
+    #[test]
+    fn vector_allocations() {
+        let (_, stats) = measure(|| {
+            let x: Vec<DualNumber<f64>> = vec![1.0_f64, 2.0_f64].into_variables();
+            x.iter()
+                .fold(DualNumber::parameter(0.0_f64), |acc, xi| acc.add_impl(xi))
+        });
+        assert_eq!(stats.operations(), 2);
+        assert_eq!(stats.chains(), 0);
+        assert!(stats.allocations() >= 2);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp, reason = "exact values are expected")]
+    fn owned_arithmetic_reuses_gradients() {
+        let x: Vec<DualNumber<f64>> = vec![1.0_f64, 2.0_f64, 4.0_f64].into_variables();
+        let mut x = x.into_iter();
+        let (Some(first), Some(second), Some(third)) = (x.next(), x.next(), x.next()) else {
+            panic!("three variables are given");
+        };
+        // the only gradient allocated is the one of the output
+        let (mut product, stats) = measure(|| first.mul_impl(&second));
+        assert_eq!(stats.allocations(), 1);
+        let ((), stats) = measure(|| {
+            let _ = product
+                .div_assign_impl(&third)
+                .sub_assign_impl(&first)
+                .mul_assign_impl(&third);
+        });
+        assert_eq!(stats.allocations(), 0);
+        let (result, stats) = measure(|| (first - second) * third / product);
+        assert_eq!(stats.allocations(), 0);
+        assert_eq!(stats.operations(), 3);
+        assert_eq!(stats.chains(), 0);
+        assert_eq!(result.value(), &2.0_f64);
+    }
+
+    #[test]
+    fn subtraction_and_division() {
+        let x: Vec<DualNumber<f64>> = vec![6.0_f64, 3.0_f64].into_variables();
+        let mut x = x.into_iter();
+        let (Some(a), Some(b)) = (x.next(), x.next()) else {
+            panic!("two variables are given");
+        };
+        for (_, stats) in [
+            measure(|| a.sub_impl(&b)),
+            measure(|| a.div_impl(&b)),
+            measure(|| a.clone() - b.clone()),
+            measure(|| a.clone() / b.clone()),
+        ] {
+            assert_eq!(stats.operations(), 1);
+            assert_eq!(stats.chains(), 0);
+        }
+    }
+
+    #[test]
+    fn sin_clones_gradient_once() {
+        let x: Vec<DualNumber<f64>> = vec![1.0_f64, 2.0_f64].into_variables();
+        for stats in [
+            measure(|| x.iter().map(Dual::sin).count()).1,
+            measure(|| x.iter().map(Dual::cos).count()).1,
+        ] {
+            assert_eq!(stats.chains(), 2);
+            assert_eq!(stats.allocations(), 2);
+        }
+        let (_, stats) = measure(|| x.iter().map(Dual::sin_cos).count());
+        assert_eq!(stats.chains(), 4);
+        assert_eq!(stats.allocations(), 4);
+    }
+
+    #[test]
+    fn preallocated_accumulator() {
+        let x: Vec<DualNumber<f64>> = vec![1.0_f64, 2.0_f64, 3.0_f64].into_variables();
+        let (total, stats) = measure(|| {
+            x.iter()
+                .fold(DualNumber::with_grad_capacity(x.len()), |mut acc, xi| {
+                    let _ = acc.add_assign_impl(xi);
+                    acc
+                })
+        });
+        assert_eq!(stats.allocations(), 1);
+        assert!(total.dual().capacity() >= 3);
+    }
+
+    #[test]
+    fn nested_measurements() {
+        let (inner, outer) = measure(|| {
+            record_operation();
+            measure(record_chain).1
+        });
+        assert_eq!(inner.chains(), 1);
+        assert_eq!(inner.operations(), 0);
+        assert_eq!(outer.chains(), 1);
+        assert_eq!(outer.operations(), 1);
+    }
+}
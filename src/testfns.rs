@@ -0,0 +1,169 @@
+//! Standard test functions for optimization, generic over [`Dual`]
+//!
+//! Handy for benchmarking minimizers and checking gradients.
+//! Multivariate functions accept slices of any length, two-dimensional ones take two arguments
+//!
+//!```
+//! use autodj::prelude::vector::*;
+//! use autodj::testfns::{himmelblau, rosenbrock};
+//!
+//! let x: Vec<DualF64> = vec![1.0, 1.0, 1.0].into_variables();
+//! let f = rosenbrock(&x);
+//! assert_eq!(f.value(), &0.0);
+//! assert_eq!(f.dual().as_ref(), &[0.0; 3]);
+//!
+//! let [x, y]: [DualF64; 2] = vec![3.0, 2.0].into_variables().try_into().unwrap();
+//! assert_eq!(himmelblau(&x, &y).decompose(), (0.0, vec![0.0, 0.0].into()));
+//! ```
+#![cfg(feature = "testfns")]
+
+use crate::{
+    fluid::{Dual, Value},
+    reduce::{dot, norm},
+};
+use num_traits::{real::Real, NumCast, One, Zero};
+
+/// Numeric constant, assuming it is representable by the value type
+fn constant<V: Value>(value: f64) -> V {
+    V::from(value).unwrap_or_else(V::zero)
+}
+
+/// `x²` of a dual number
+fn square<D: Dual>(x: &D) -> D {
+    x.mul_impl(x)
+}
+
+/// Sphere function `∑ xᵢ²` with minimum `0` at the origin
+#[must_use]
+pub fn sphere<D: Dual>(x: &[D]) -> D {
+    dot(x, x)
+}
+
+/// Rosenbrock function `∑ 100·(xᵢ₊₁ - xᵢ²)² + (1 - xᵢ)²` with minimum `0` at `(1, …, 1)`
+#[must_use]
+pub fn rosenbrock<D: Dual>(x: &[D]) -> D {
+    let hundred = D::parameter(constant(100.0));
+    x.iter()
+        .zip(x.iter().skip(1))
+        .fold(D::parameter(D::Value::zero()), |acc, (current, next)| {
+            let valley = next.sub_impl(&square(current));
+            let offset = D::parameter(D::Value::one()).sub_impl(current);
+            acc.add_impl(&hundred.mul_impl(&square(&valley)))
+                .add_impl(&square(&offset))
+        })
+}
+
+/// Rastrigin function `10·n + ∑ xᵢ² - 10·cos(2π·xᵢ)` with minimum `0` at the origin
+#[must_use]
+pub fn rastrigin<D: Dual>(x: &[D]) -> D {
+    let ten = D::parameter(constant(10.0));
+    let frequency = D::parameter(constant(core::f64::consts::TAU));
+    x.iter().fold(D::parameter(D::Value::zero()), |acc, x| {
+        let ripple = ten.mul_impl(&frequency.mul_impl(x).cos());
+        acc.add_impl(&ten).add_impl(&square(x)).sub_impl(&ripple)
+    })
+}
+
+/// Ackley function with minimum `0` at the origin
+#[must_use]
+pub fn ackley<D: Dual>(x: &[D]) -> D {
+    let count: D::Value = NumCast::from(x.len()).unwrap_or_else(D::Value::one);
+    let frequency = D::parameter(constant(core::f64::consts::TAU));
+    let spread = norm(x).chain(|&norm| {
+        let scale = constant::<D::Value>(-0.2) / count.sqrt();
+        let value = constant::<D::Value>(-20.0) * (scale * norm).exp();
+        (value, value * scale)
+    });
+    let ripple = x
+        .iter()
+        .fold(D::parameter(D::Value::zero()), |acc, x| {
+            acc.add_impl(&frequency.mul_impl(x).cos())
+        })
+        .chain(|&sum| {
+            let value = (sum / count).exp();
+            (value, value / count)
+        });
+    let offset = constant::<D::Value>(20.0) + D::Value::one().exp();
+    spread.sub_impl(&ripple).add_impl(&D::parameter(offset))
+}
+
+/// Himmelblau function `(x² + y - 11)² + (x + y² - 7)²` with four minima `0`,
+/// e.g. at `(3, 2)`
+#[must_use]
+pub fn himmelblau<D: Dual>(x: &D, y: &D) -> D {
+    let first = square(x)
+        .add_impl(y)
+        .sub_impl(&D::parameter(constant(11.0)));
+    let second = x
+        .add_impl(&square(y))
+        .sub_impl(&D::parameter(constant(7.0)));
+    square(&first).add_impl(&square(&second))
+}
+
+/// Booth function `(x + 2y - 7)² + (2x + y - 5)²` with minimum `0` at `(1, 3)`
+#[must_use]
+pub fn booth<D: Dual>(x: &D, y: &D) -> D {
+    let two = D::parameter(constant(2.0));
+    let first = x
+        .add_impl(&two.mul_impl(y))
+        .sub_impl(&D::parameter(constant(7.0)));
+    let second = two
+        .mul_impl(x)
+        .add_impl(y)
+        .sub_impl(&D::parameter(constant(5.0)));
+    square(&first).add_impl(&square(&second))
+}
+
+/// Beale function `(1.5 - x + xy)² + (2.25 - x + xy²)² + (2.625 - x + xy³)²`
+/// with minimum `0` at `(3, 0.5)`
+#[must_use]
+pub fn beale<D: Dual>(x: &D, y: &D) -> D {
+    [1.5, 2.25, 2.625]
+        .into_iter()
+        .fold(
+            (D::parameter(D::Value::zero()), y.clone()),
+            |(acc, power), offset| {
+                let term = D::parameter(constant(offset))
+                    .sub_impl(x)
+                    .add_impl(&x.mul_impl(&power));
+                (acc.add_impl(&square(&term)), power.mul_impl(y))
+            },
+        )
+        .0
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+    use crate::solid::array::{DualNumber, IntoVariables};
+
+    /// Value and gradient norm at a point
+    fn at(
+        func: impl Fn(&DualNumber<f64, 2>, &DualNumber<f64, 2>) -> DualNumber<f64, 2>,
+        point: [f64; 2],
+    ) -> (f64, f64) {
+        let [x, y] = point.into_variables();
+        let (value, grad) = func(&x, &y).decompose();
+        (value, grad.as_ref().iter().map(|deriv| deriv.abs()).sum())
+    }
+
+    #[test]
+    fn known_minima() {
+        assert!(at(himmelblau, [3.0_f64, 2.0_f64]).0.abs() < 1e-12_f64);
+        assert!(at(booth, [1.0_f64, 3.0_f64]).1 < 1e-12_f64);
+        assert!(at(beale, [3.0_f64, 0.5_f64]).1 < 1e-12_f64);
+        assert!(at(|x, y| rastrigin(&[*x, *y]), [0.0_f64, 0.0_f64]).0 < 1e-12_f64);
+        assert!(at(|x, y| ackley(&[*x, *y]), [0.0_f64, 0.0_f64]).0 < 1e-12_f64);
+        assert!(at(|x, y| sphere(&[*x, *y]), [0.0_f64, 0.0_f64]).1 < 1e-12_f64);
+    }
+
+    #[test]
+    fn rosenbrock_gradient() {
+        // ∂f/∂x = -400·x·(y - x²) - 2·(1 - x), ∂f/∂y = 200·(y - x²)
+        let [x, y] = [0.0_f64, 1.0_f64].into_variables();
+        let (value, grad) = rosenbrock(&[x, y]).decompose();
+        assert!((value - 101.0_f64).abs() < 1e-12_f64);
+        assert_eq!(grad.as_ref(), &[-2.0_f64, 200.0_f64]);
+    }
+}
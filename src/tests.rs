@@ -1 +1,131 @@
+//! Crate-wide unit tests
 
+use crate::{fluid::Dual, solid::single::IntoVariable};
+use core::f64::consts::E;
+
+/// Without `std`, float intrinsics behind default methods of [`Dual`] come from `libm`
+#[test]
+fn elementary_functions_without_std() {
+    let x = 1.0_f64.into_variable();
+    let close = |(value, deriv): (f64, f64), (expected_value, expected_deriv): (f64, f64)| {
+        (value - expected_value).abs() < 1e-15_f64 && (deriv - expected_deriv).abs() < 1e-15_f64
+    };
+    assert!(close(x.exp().decompose(), (E, E)));
+    assert!(close(x.ln().decompose(), (0.0_f64, 1.0_f64)));
+    assert!(close(x.powf(3.0_f64).decompose(), (1.0_f64, 3.0_f64)));
+    let (sin, cos) = x.sin_cos();
+    assert!(close(sin.decompose(), (1.0_f64.sin(), 1.0_f64.cos())));
+    assert!(close(cos.decompose(), (1.0_f64.cos(), -1.0_f64.sin())));
+}
+
+#[test]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+fn powf_edge_cases() {
+    let negative = (-2.0_f64).into_variable();
+    assert_eq!(negative.powf(3.0_f64).decompose(), (-8.0_f64, 12.0_f64));
+    assert_eq!(negative.powf(-1.0_f64).decompose(), (-0.5_f64, -0.25_f64));
+    assert_eq!(
+        0.0_f64.into_variable().powf(1.0_f64).decompose(),
+        (0.0_f64, 1.0_f64)
+    );
+    // x^(exp-1) overflows while x^exp does not
+    assert_eq!(
+        1e-310_f64.into_variable().powf(0.0_f64).decompose(),
+        (1.0_f64, 0.0_f64)
+    );
+}
+
+#[test]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+#[cfg_attr(
+    all(feature = "nan-check", debug_assertions),
+    should_panic(expected = "powf of [0.0] gives non-finite derivative inf")
+)]
+fn powf_non_finite_at_zero() {
+    let zero = 0.0_f64.into_variable();
+    assert_eq!(zero.powf(0.5_f64).value(), &0.0_f64);
+    assert_eq!(
+        zero.powf(-1.0_f64).decompose(),
+        (f64::INFINITY, f64::NEG_INFINITY)
+    );
+}
+
+#[test]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+#[cfg_attr(
+    all(feature = "nan-check", debug_assertions),
+    should_panic(expected = "powf of [inf] gives non-finite value inf")
+)]
+fn powf_non_finite_at_infinity() {
+    assert_eq!(
+        f64::INFINITY.into_variable().powf(0.5_f64).value(),
+        &f64::INFINITY
+    );
+}
+
+#[test]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+#[cfg_attr(
+    all(feature = "nan-check", debug_assertions),
+    should_panic(expected = "gives non-finite derivative -inf")
+)]
+fn powf_overflowing_derivative() {
+    // x^(exp-1) overflows while x^exp does not
+    assert_eq!(
+        1e-300_f64.into_variable().powf(-1.0_f64).decompose(),
+        (1e-300_f64.powi(-1), f64::NEG_INFINITY)
+    );
+    assert_eq!(
+        1e-200_f64.into_variable().powf(-1.5_f64).decompose(),
+        (1e-200_f64.powf(-1.5_f64), f64::NEG_INFINITY)
+    );
+}
+
+#[test]
+fn domain_checks() {
+    use crate::fluid::DomainError;
+
+    let [negative, zero, positive, nan] =
+        [-4.0_f64, 0.0_f64, 4.0_f64, f64::NAN].map(IntoVariable::into_variable);
+    assert_eq!(
+        positive.try_sqrt().map(Dual::decompose),
+        Ok((2.0_f64, 0.25_f64))
+    );
+    assert_eq!(zero.try_sqrt(), Err(DomainError { function: "sqrt" }));
+    assert!(nan.try_ln().is_err());
+    assert_eq!(
+        negative.try_powf(2.0_f64).map(Dual::decompose),
+        Ok((16.0_f64, -8.0_f64))
+    );
+    assert!(negative.try_powf(0.5_f64).is_err());
+    assert_eq!(
+        zero.try_powf(1.0_f64).map(Dual::decompose),
+        Ok((0.0_f64, 1.0_f64))
+    );
+    assert!(zero.try_powf(0.5_f64).is_err());
+    assert!(zero.try_recip().is_err());
+    assert!(positive.try_powf(f64::NAN).is_err());
+    assert!(positive.try_div(&nan).is_err());
+    assert_eq!(
+        positive.try_div(&zero),
+        Err(DomainError { function: "div" })
+    );
+    assert_eq!(
+        positive.try_div(&negative).map(|q| *q.value()),
+        Ok(-1.0_f64)
+    );
+}
+
+#[test]
+fn checked_division() {
+    use crate::fluid::Grad;
+
+    let x = 3.0_f64.into_variable();
+    let zero = x.sub_impl(&x);
+    assert_eq!(x.checked_div(&zero), None);
+    assert_eq!(
+        x.checked_div(&x.mul_impl(&x)).map(Dual::decompose),
+        Some((1.0_f64 / 3.0_f64, -1.0_f64 / 9.0_f64))
+    );
+    assert_eq!(Grad::checked_div(2.0_f64, f64::NAN), None);
+}
@@ -0,0 +1,106 @@
+//! Interoperability with the [`uom`] crate
+//!
+//! Dual numbers carry magnitudes of [`uom::si`] quantities in base units,
+//! so models are checked dimensionally where quantities enter and leave them,
+//! while derivatives come out as quantities of the ratio of dimensions
+//!
+//!```
+//! use autodj::prelude::array::*;
+//! use autodj::units::{derivative, BaseMagnitude};
+//! use uom::si::{
+//!     energy::joule, f64::{Energy, Mass, Momentum, Velocity},
+//!     mass::gram, momentum::kilogram_meter_per_second, velocity::meter_per_second,
+//! };
+//!
+//! let mass = Mass::new::<gram>(2000.0);
+//! let speed = Velocity::new::<meter_per_second>(3.0);
+//! let [m, v] = [mass.base(), speed.base()].into_variables();
+//! let kinetic = m * v * v / 2.0;
+//!
+//! assert_eq!(Energy::from_base(*kinetic.value()).get::<joule>(), 9.0);
+//! let momentum: Momentum = derivative::<Energy, Velocity>(kinetic.dual()[1]);
+//! assert_eq!(momentum.get::<kilogram_meter_per_second>(), 6.0);
+//! ```
+#![cfg(feature = "uom")]
+
+use crate::fluid::Value;
+use core::{marker::PhantomData, ops::Div};
+use uom::{
+    si::{Dimension, Quantity, Units},
+    Conversion,
+};
+
+/// Quantities convertible to and from their magnitudes in base units
+pub trait BaseMagnitude: Sized {
+    /// Underlying storage type
+    type Storage: Value;
+
+    /// Magnitude in base units, e.g. to seed a variable
+    fn base(&self) -> Self::Storage;
+
+    /// Quantity of a magnitude in base units, e.g. of a dual value
+    fn from_base(magnitude: Self::Storage) -> Self;
+}
+
+impl<D, U, V> BaseMagnitude for Quantity<D, U, V>
+where
+    D: Dimension + ?Sized,
+    U: Units<V> + ?Sized,
+    V: Value + Conversion<V>,
+{
+    type Storage = V;
+
+    fn base(&self) -> V {
+        self.value
+    }
+
+    fn from_base(magnitude: V) -> Self {
+        Self {
+            dimension: PhantomData,
+            units: PhantomData,
+            value: magnitude,
+        }
+    }
+}
+
+/// Derivative of an `Output` quantity with respect to an `Input` one
+/// from the derivative of their magnitudes in base units
+pub fn derivative<Output, Input>(deriv: Output::Storage) -> <Output as Div<Input>>::Output
+where
+    Output: BaseMagnitude + Div<Input>,
+    Input: BaseMagnitude<Storage = Output::Storage>,
+{
+    Output::from_base(deriv) / Input::from_base(num_traits::one())
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, reason = "exact values are expected")]
+mod tests {
+    use super::*;
+    use crate::{fluid::Dual, solid::single::IntoVariable};
+    use uom::si::{
+        f64::{Length, Time, Velocity},
+        length::{kilometer, meter},
+        time::hour,
+        velocity::meter_per_second,
+    };
+
+    #[test]
+    fn base_units_round_trip() {
+        let length = Length::new::<kilometer>(1.5_f64);
+        assert_eq!(length.base(), 1500.0_f64);
+        assert_eq!(Length::from_base(length.base()), length);
+    }
+
+    #[test]
+    fn derivative_dimension() {
+        let time = Time::new::<hour>(1.0_f64).base().into_variable();
+        let distance = time.mul_impl(&time) * 2.0_f64;
+        let speed: Velocity = derivative::<Length, Time>(*distance.dual());
+        assert_eq!(speed.get::<meter_per_second>(), 14400.0_f64);
+        assert_eq!(
+            Length::from_base(*distance.value()).get::<meter>(),
+            2.0_f64 * 3600.0_f64 * 3600.0_f64
+        );
+    }
+}
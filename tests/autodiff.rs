@@ -5,7 +5,8 @@
     clippy::default_numeric_fallback,
     clippy::indexing_slicing,
     clippy::similar_names,
-    clippy::float_cmp
+    clippy::float_cmp,
+    reason = "tests"
 )]
 
 use autodiff::{F, F1};
@@ -33,10 +34,10 @@ fn quadratic() {
     assert_eq!(autodj.dual().to_owned(), autodiff);
 
     println!(
-        r#"
+        r"
 ----------f(x) = (x - 1)^2
 autodiff: df/dx = {autodiff} at x = {x}
-autodj  : f({x}) ≈ {autodj}"#
+autodj  : f({x}) ≈ {autodj}"
     );
 }
 
@@ -58,8 +59,7 @@ fn multi_quadratic() {
 
     let autodj = {
         fn calculate_multi_quadratic([x, y]: [DualNumber<f64, 2>; 2]) -> DualNumber<f64, 2> {
-            let shift = 1.0.into();
-            (x - shift) * (y * 2.0.into() - shift)
+            (x - 1.0) * (y * 2.0 - 1.0)
         }
         calculate_multi_quadratic([x, y].into_variables())
     };
@@ -67,10 +67,10 @@ fn multi_quadratic() {
     assert_eq!(autodj.dual().as_ref(), &autodiff);
 
     println!(
-        r#"
+        r"
 ----------f(x,y) = (x - 1) * (2 * y - 1)
 autodiff: df/dx = {} and df/dy = {} at x = {}, y = {}
-autodj  : f({:?}) ≈ {}"#,
+autodj  : f({:?}) ≈ {}",
         autodiff[0],
         autodiff[1],
         x,
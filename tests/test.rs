@@ -3,7 +3,8 @@
     clippy::default_numeric_fallback,
     clippy::expect_used,
     clippy::float_cmp,
-    clippy::indexing_slicing
+    clippy::indexing_slicing,
+    reason = "tests"
 )]
 
 mod ideal_gas {
@@ -40,9 +41,9 @@ mod ideal_gas {
         let state = moles.into_variable().map(scalar_func);
 
         println!(
-            r#"
+            r"
 Initial guess: r({moles_initial}) = {initial}
-Update-------: r({moles}) = {:e}"#,
+Update-------: r({moles}) = {:e}",
             state.value()
         );
     }
@@ -73,9 +74,9 @@ Update-------: r({moles}) = {:e}"#,
         let update = vector_func(&[moles, volume].into_variables());
 
         println!(
-            r#"
+            r"
 Initial guess: r({moles_initial}, {volume_initial}) = {initial:e}
-Update-------: r({moles}, {volume}) = {:e}"#,
+Update-------: r({moles}, {volume}) = {:e}",
             update.value()
         );
     }
@@ -101,6 +102,16 @@ mod vector {
         assert_eq!(result.dual().as_ref(), &[2., 4., 6.]);
     }
 
+    #[test]
+    fn indexed_slots() {
+        let mut seed = autodj::solid::vector::Grad::from(vec![0.0; 3]);
+        seed[1] = 2.0;
+        let x = DualF64::new(4.0, seed);
+        let f = x.clone() * x;
+        assert_eq!(f.dual()[1], 16.0);
+        assert_eq!(f.dual()[0], 0.0);
+    }
+
     #[test]
     fn sum() {
         let x = vec![1., 2., 3., 4., 5.];
@@ -114,7 +125,7 @@ mod vector {
             .into_iter()
             .reduce(Add::add)
             .expect("nonzero slice length");
-        println!("f({x:?}) = {result:?}",);
+        println!("f({x:?}) = {result:?}");
 
         assert_eq!(result.value(), &reference);
     }
@@ -127,7 +138,7 @@ mod vector {
         println!("f({x:?}) = ∏ x_i = {reference}");
 
         let result = x.clone().into_variables().into_iter().reduce(Mul::mul);
-        println!("f({x:?}) = {result:?}",);
+        println!("f({x:?}) = {result:?}");
 
         assert_eq!(
             result.map(|result| result.value().to_owned()),
@@ -135,6 +146,19 @@ mod vector {
         );
     }
 
+    #[test]
+    fn sum_and_product_traits() {
+        let x = vec![1., 2., 3.].into_variables();
+
+        let total: DualF64 = x.iter().sum();
+        assert_eq!(total.value(), &6.0);
+        assert_eq!(total.dual().as_ref(), &[1.0, 1.0, 1.0]);
+
+        let product: DualF64 = x.into_iter().product();
+        assert_eq!(product.value(), &6.0);
+        assert_eq!(product.dual().as_ref(), &[6.0, 3.0, 2.0]);
+    }
+
     #[test]
     fn product_owned() {
         let zero = [1.0, 2.0, 3.0]
@@ -174,4 +198,255 @@ mod vector {
         assert_eq!(df.as_ref(), &[0.5, -0.25]);
         assert_eq!(f, 0.5);
     }
+
+    #[test]
+    fn scalar_operands() {
+        let [x, y]: [DualF64; 2] = [1., 2.].into_variables().try_into().expect("two");
+
+        let (f, df) = (x * 2.0 + 1.0 - y / 4.0).decompose();
+        assert_eq!(df.as_ref(), &[2.0, -0.25]);
+        assert_eq!(f, 2.5);
+    }
+
+    #[test]
+    fn scalar_comparisons() {
+        let [x, y]: [DualF64; 2] = [1., 2.].into_variables().try_into().expect("two");
+        assert!(x > 0.0 && x <= 1.0 && x == 1.0);
+        assert!(y != 1.0 && y > *x.value());
+    }
+
+    #[test]
+    fn negated_references() {
+        let x = vec![1., 2.].into_variables();
+        let negated: Vec<DualF64> = x.iter().map(|xi| -xi).collect();
+        assert_eq!(negated[1].value(), &-2.0);
+        assert_eq!(negated[1].dual().as_ref(), &[-0.0, -1.0]);
+    }
+
+    #[test]
+    fn scalar_left_operands() {
+        let [x, y]: [DualF64; 2] = [1., 2.].into_variables().try_into().expect("two");
+
+        let (f, df) = (2.0 * x - 3.0 * y.clone() + 1.0 / y).decompose();
+        assert_eq!(df.as_ref(), &[2.0, -3.25]);
+        assert_eq!(f, -3.5);
+    }
+
+    #[test]
+    fn single_precision() {
+        let x: Vec<DualF32> = vec![4.0_f32, 3.0].into_variables();
+        let f = x[0].powf(0.5).mul_impl(&x[1]);
+        assert_eq!(f.value(), &6.0);
+        assert!((f.dual().as_ref()[0] - 0.75).abs() <= f32::EPSILON);
+        assert!((f.dual().as_ref()[1] - 2.0).abs() <= f32::EPSILON);
+    }
+}
+
+mod array {
+    use autodj::prelude::array::*;
+
+    #[test]
+    fn many_variables() {
+        // no upper bound on the number of statically-known variables
+        let values: [f64; 128] =
+            std::array::from_fn(|i| f64::from(u8::try_from(i).expect("small")));
+        let x: [DualNumber<f64, 128>; 128] = values.into_variables();
+        let f: DualNumber<f64, 128> = x.iter().sum();
+        assert_eq!(f.value(), &8128.0);
+        assert!(f.dual().as_ref().iter().all(|&deriv| deriv == 1.0));
+    }
+
+    #[test]
+    fn expression_surface() {
+        let zero = DualNumber::<f64, 3>::default();
+        assert_eq!(zero, DualNumber::parameter(0.0));
+        let offset: DualNumber<f64, 3> = 1.5.into();
+        let [x, _, z] = [1.0, 2.0, 3.0].into_variables();
+        let f: DualNumber<f64, 3> = 2.0 * x + z / 2.0 - offset;
+        assert_eq!(f.value(), &2.0);
+        assert_eq!(f.dual().as_ref(), &[2.0, 0.0, 0.5]);
+        assert_eq!(std::format!("{zero}"), "0+[0.0, 0.0, 0.0]∆");
+    }
+
+    #[test]
+    fn single_precision() {
+        let [x, y]: [DualF32<2>; 2] = [1.0, 2.0].into_variables();
+        let f = (x * y).exp().ln() / y.recip() - x.sin_cos().0.signum();
+        assert!((f.value() - 3.0).abs() <= f32::EPSILON);
+        assert!((f.dual().as_ref()[0] - 4.0).abs() <= 4.0 * f32::EPSILON);
+        assert!((f.dual().as_ref()[1] - 4.0).abs() <= 4.0 * f32::EPSILON);
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let special = DualNumber::new(-1e-7, [f64::NAN, f64::INFINITY, -0.0].into());
+        for f in [DualF64::<3>::default(), special] {
+            let parsed = f.to_string().parse::<DualF64<3>>();
+            assert_eq!(parsed.map(|parsed| parsed.to_string()), Ok(f.to_string()));
+        }
+        let single = "2.5NaN∆".parse::<autodj::solid::single::DualF32>();
+        assert_eq!(
+            single.map(|x| (*x.value(), x.dual().is_nan())),
+            Ok((2.5, true))
+        );
+        assert!("+[1.0, 2.0]∆".parse::<DualF64<2>>().is_err());
+    }
+}
+
+mod calibration {
+    use autodj::calibration::Calibration;
+    use autodj::prelude::vector::*;
+    use autodj::solver::Termination;
+
+    #[test]
+    fn exponential_decay() {
+        // y = amplitude * exp(-rate * t) with amplitude = 2, rate = 0.5
+        let data: Vec<(f64, f64)> = (0..5)
+            .map(f64::from)
+            .map(|t| (t, 2.0 * (-0.5 * t).exp()))
+            .collect();
+        let residual = |p: &[DualF64]| {
+            data.iter()
+                .map(|&(t, y)| {
+                    let decay = p[1].mul_impl(&(-t).into()).exp();
+                    p[0].mul_impl(&decay) - y
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let report = Calibration::new([("amplitude", 1.0), ("rate", 1.0)])
+            .tolerance(1e-12)
+            .check_gradients()
+            .run(&residual);
+        println!("{report}");
+
+        assert!(report.converged());
+        assert!(report.gradient_error().expect("requested") < 1e-6);
+        assert!((report.parameter("amplitude").expect("named") - 2.0).abs() < 1e-9);
+        assert!((report.parameter("rate").expect("named") - 0.5).abs() < 1e-9);
+        assert_eq!(report.parameter("unknown"), None);
+    }
+
+    #[test]
+    fn singular_jacobian() {
+        // both parameters enter as a sum only
+        let residual = |p: &[DualF64]| vec![p[0].add_impl(&p[1]) - 1.0];
+
+        let report = Calibration::new([("a", 0.0), ("b", 0.0)]).run(&residual);
+        assert_eq!(report.termination(), Termination::SingularJacobian);
+        assert!(!report.converged());
+        assert!(report
+            .to_string()
+            .starts_with("singular Jacobian after 0 iterations"));
+
+        let damped = Calibration::new([("a", 0.0), ("b", 0.0)])
+            .damping(1e-9)
+            .run(&residual);
+        assert!(damped.converged());
+    }
+}
+
+#[cfg(feature = "uuid")]
+mod variable_space {
+    use autodj::prelude::uuid::*;
+
+    #[test]
+    fn ordered_columns() {
+        let mut space = sparse::VariableSpace::new();
+        let variables = space.variables([1.0, 2.0, 3.0, 4.0]);
+        let rows: Vec<_> = variables
+            .iter()
+            .rev()
+            .map(|x| x.mul_impl(&2.0.into()))
+            .collect();
+        let jacobian = space.jacobian(&rows);
+        assert_eq!(jacobian.len(), 16);
+        for (row, derivs) in jacobian.chunks(4).enumerate() {
+            for (column, &deriv) in derivs.iter().enumerate() {
+                let expected = if row + column == 3 { 2.0 } else { 0.0 };
+                assert_eq!(deriv, expected);
+            }
+        }
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn nalgebra_matrix() {
+        let mut space = sparse::VariableSpace::new();
+        let [x, y] = [space.variable(2.0), space.variable(3.0)];
+        let f = x.mul_impl(&y);
+        let matrix = space.jacobian_matrix(&[f, y]);
+        assert_eq!(matrix, nalgebra::dmatrix![3.0, 2.0; 0.0, 1.0]);
+    }
+}
+
+#[cfg(feature = "sparse")]
+mod indexed {
+    use autodj::prelude::indexed::*;
+
+    #[test]
+    fn unique_keys() {
+        let x = 1.0.into_variable();
+        let y = 2.0.into_variable();
+        let f = x.add_impl(&y);
+        assert_eq!(f.dual().as_ref().len(), 2);
+    }
+
+    #[test]
+    fn single_precision() {
+        let x: DualF32 = 3.0.into_variable();
+        let f = x.mul_impl(&x).sub_impl(&DualF32::parameter(1.0));
+        assert_eq!(f.value(), &8.0);
+        assert_eq!(
+            f.dual().as_ref().values().copied().collect::<Vec<f32>>(),
+            [6.0]
+        );
+    }
+}
+
+#[cfg(feature = "sparse")]
+mod named {
+    use autodj::prelude::named::*;
+
+    #[test]
+    fn owned_names() {
+        let names = ["k", "m", "c"];
+        let variables: Vec<DualNumber<f64>> = names
+            .iter()
+            .zip([1.0, 2.0, 3.0])
+            .map(|(&name, value)| value.into_variable_named(name.to_owned()))
+            .collect();
+        let f = variables
+            .iter()
+            .fold(DualNumber::parameter(0.0), |acc, x| acc.add_impl(x));
+        assert_eq!(f.value(), &6.0);
+        for name in names {
+            assert_eq!(f.dual().as_ref().get(name), Some(&1.0));
+        }
+    }
+
+    #[test]
+    fn labelled_display() {
+        let x: DualNumber<f64> = 3.25.into_variable_named("pressure".to_owned());
+        let f = x.mul_impl(&x);
+        assert_eq!(std::format!("{f:#.1}"), "f = 10.6, ∂f/∂pressure = 6.5");
+    }
+}
+
+#[cfg(feature = "sparse")]
+mod custom_hasher {
+    use autodj::fluid::Dual;
+    use autodj::solid::sparse::DualNumber;
+    use std::hash::{BuildHasherDefault, DefaultHasher};
+
+    type Deterministic<Key> = DualNumber<f64, Key, BuildHasherDefault<DefaultHasher>>;
+
+    #[test]
+    fn deterministic_hasher() {
+        let x: Deterministic<u8> = Dual::new(2.0, [(0, 1.0)].into_iter().collect());
+        let y: Deterministic<u8> = Dual::new(3.0, [(1, 1.0)].into_iter().collect());
+        let f = x.mul_impl(&y);
+        assert_eq!(f.dual().as_ref().get(&0), Some(&3.0));
+        assert_eq!(f.dual().as_ref().get(&1), Some(&2.0));
+    }
 }